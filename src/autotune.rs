@@ -0,0 +1,289 @@
+//! Inductor DCR current sense calibration: converting the autotune codes
+//! to and from physical inductor values, and saving the auto-tuned codes
+//! so they can be forced on later boots instead of re-calibrating.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{AutotuneForce, ForceAutotune, ForceGmAdjust, ForceUpdate, GmAdjustForce};
+use crate::{Bq2577x, Error};
+
+/// Highest GM_ADJUST code.
+const GM_ADJUST_MAX: u8 = 0x3f;
+
+/// Time between FORCE_UPDATE polls, in milliseconds.
+const FORCE_UPDATE_POLL_MS: u32 = 1;
+
+/// Number of FORCE_UPDATE polls after which [`Error::Timeout`] is
+/// reported.
+const FORCE_UPDATE_MAX_POLLS: u32 = 100;
+
+/// AUTOTUNE code for an inductor time constant L(µH)/DCR(mΩ), or `None`
+/// if the time constant is outside what the code can represent.
+pub fn autotune_code(l_uh: f32, dcr_mohm: f32) -> Option<u8> {
+    let code = libm::roundf(256.0 - 265.0 * l_uh / dcr_mohm);
+
+    (0.0..=255.0).contains(&code).then_some(code as u8)
+}
+
+/// Inductor time constant L(µH)/DCR(mΩ) for an AUTOTUNE code.
+pub fn time_constant(code: u8) -> f32 {
+    (256.0 - code as f32) / 265.0
+}
+
+/// GM_ADJUST code for an inductor DCR in milliohms, or `None` if the DCR
+/// is outside what can be forced. A forced GM_ADJUST is never 0, as the
+/// charger adds 1 to the forced value.
+pub fn gm_adjust_code(dcr_mohm: f32) -> Option<u8> {
+    let code = libm::roundf(71.25 - 272.0 / dcr_mohm);
+
+    (1.0..=GM_ADJUST_MAX as f32).contains(&code).then_some(code as u8)
+}
+
+/// Inductor DCR in milliohms for a GM_ADJUST code.
+pub fn dcr_mohm(code: u8) -> f32 {
+    272.0 / (71.25 - code as f32)
+}
+
+/// Calibration codes for both phases, small enough to persist in
+/// non-volatile storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Autotune {
+    /// Phase A AUTOTUNE code.
+    pub autotune_a: u8,
+
+    /// Phase B AUTOTUNE code.
+    pub autotune_b: u8,
+
+    /// GM_ADJUST code in effect.
+    pub gm_adjust: u8,
+}
+
+impl Autotune {
+    /// Length of the serialized form, in bytes.
+    pub const LEN: usize = 3;
+
+    /// Codes for two identical inductors, or `None` if the values are
+    /// outside what the codes can represent.
+    pub fn from_inductor(l_uh: f32, dcr_mohm: f32) -> Option<Self> {
+        let autotune = autotune_code(l_uh, dcr_mohm)?;
+
+        Some(Self {
+            autotune_a: autotune,
+            autotune_b: autotune,
+            gm_adjust: gm_adjust_code(dcr_mohm)?,
+        })
+    }
+
+    /// Serializes the codes.
+    pub const fn to_bytes(&self) -> [u8; Self::LEN] {
+        [self.autotune_a, self.autotune_b, self.gm_adjust]
+    }
+
+    /// Deserializes codes saved by [`Self::to_bytes`], or returns
+    /// `None` if they aren't valid.
+    pub const fn from_bytes(bytes: [u8; Self::LEN]) -> Option<Self> {
+        let [autotune_a, autotune_b, gm_adjust] = bytes;
+
+        // see gm_adjust_code()
+        if gm_adjust == 0 || gm_adjust > GM_ADJUST_MAX {
+            return None;
+        }
+
+        Some(Self {
+            autotune_a,
+            autotune_b,
+            gm_adjust,
+        })
+    }
+
+    /// Phase A inductor time constant L(µH)/DCR(mΩ).
+    pub fn time_constant_a(&self) -> f32 {
+        time_constant(self.autotune_a)
+    }
+
+    /// Phase B inductor time constant L(µH)/DCR(mΩ).
+    pub fn time_constant_b(&self) -> f32 {
+        time_constant(self.autotune_b)
+    }
+
+    /// Inductor DCR in milliohms.
+    pub fn dcr_mohm(&self) -> f32 {
+        dcr_mohm(self.gm_adjust)
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Reads the codes found by the charger's own calibration. Returns
+    /// `None` while the converter is off, as the charger clears them
+    /// then, and while either force enable is set, as the codes are then
+    /// the forced ones rather than calibrated. Both force enables are set
+    /// at POR; see [`Self::release_autotune`].
+    pub async fn capture_autotune(&mut self) -> Result<Option<Autotune>, Error<IFACE::Error>> {
+        let gm = self.gm_adjust_force().await?;
+
+        if gm.force_autotune_en() == ForceAutotune::Enable
+            || gm.force_gm_adjust_en() == ForceGmAdjust::Enable
+            || gm.gm_adjust() == 0
+        {
+            return Ok(None);
+        }
+
+        let read = self.autotune_read().await?;
+
+        Ok(Some(Autotune {
+            autotune_a: read.autotune_a(),
+            autotune_b: read.autotune_b(),
+            gm_adjust: gm.gm_adjust() as u8,
+        }))
+    }
+
+    /// Clears the force enables and restarts the converter, so that the
+    /// charger calibrates itself for [`Self::capture_autotune`]. The
+    /// converter briefly stops; this waits for it to restart.
+    pub async fn release_autotune<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Error<IFACE::Error>> {
+        let gm = self
+            .gm_adjust_force()
+            .await?
+            .with_force_autotune_en(ForceAutotune::Disable)
+            .with_force_gm_adjust_en(ForceGmAdjust::Disable);
+
+        self.force_update(delay, gm).await
+    }
+
+    /// Forces previously captured codes, skipping the charger's own
+    /// calibration from now on. The converter briefly stops while the
+    /// charger takes the new values; this waits for it to finish.
+    pub async fn apply_autotune<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        autotune: Autotune,
+    ) -> Result<(), Error<IFACE::Error>> {
+        self.set_autotune_force(
+            AutotuneForce::new()
+                .with_force_autotune_a(autotune.autotune_a)
+                .with_force_autotune_b(autotune.autotune_b),
+        )
+        .await?;
+
+        // the charger applies FORCE_GM_ADJUST + 1
+        let gm = self
+            .gm_adjust_force()
+            .await?
+            .with_force_autotune_en(ForceAutotune::Enable)
+            .with_force_gm_adjust_en(ForceGmAdjust::Enable)
+            .with_force_gm_adjust(autotune.gm_adjust.saturating_sub(1) as u16);
+
+        self.force_update(delay, gm).await
+    }
+
+    /// Writes `gm` and has the converter take it, waiting for the
+    /// update to finish.
+    async fn force_update<D: DelayNs>(&mut self, delay: &mut D, gm: GmAdjustForce) -> Result<(), Error<IFACE::Error>> {
+        let gm = gm.with_force_update(ForceUpdate::Idle);
+
+        // FORCE_UPDATE acts on a 0b to 1b transition
+        self.set_gm_adjust_force(gm).await?;
+        self.set_gm_adjust_force(gm.with_force_update(ForceUpdate::Update))
+            .await?;
+
+        for _ in 0..FORCE_UPDATE_MAX_POLLS {
+            delay.delay_ms(FORCE_UPDATE_POLL_MS).await;
+
+            if self.gm_adjust_force().await?.force_update() == ForceUpdate::Idle {
+                return Ok(());
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{AutotuneRead, Register};
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_autotune_units() {
+        // POR GM_ADJUST refers to 12.2mΩ
+        assert_eq!(gm_adjust_code(12.2), Some(0x31));
+        assert!((dcr_mohm(0x31) - 12.2).abs() < 0.1);
+        assert_eq!(gm_adjust_code(1.0), None);
+
+        assert_eq!(autotune_code(1.0, 10.0), Some(230));
+        assert!((time_constant(230) - 0.1).abs() < 0.01);
+        assert_eq!(autotune_code(10.0, 1.0), None);
+
+        let autotune = Autotune::from_inductor(1.0, 10.0).unwrap();
+        assert_eq!(Autotune::from_bytes(autotune.to_bytes()), Some(autotune));
+        assert_eq!(Autotune::from_bytes([0xa8, 0xa8, 0x40]), None);
+        assert_eq!(Autotune::from_bytes([0xa8, 0xa8, 0]), None);
+    }
+
+    #[tokio::test]
+    async fn test_capture_and_apply_autotune() {
+        // POR forces both codes, here with the converter switching
+        let por = GmAdjustForce::from_bits(GmAdjustForce::RESET).with_gm_adjust(0x32);
+        let released = por
+            .with_force_autotune_en(ForceAutotune::Disable)
+            .with_force_gm_adjust_en(ForceGmAdjust::Disable);
+        let tuned = released.with_gm_adjust(0x20);
+        let forced = tuned
+            .with_force_autotune_en(ForceAutotune::Enable)
+            .with_force_gm_adjust_en(ForceGmAdjust::Enable)
+            .with_force_gm_adjust(0x1f);
+
+        let e = vec![
+            read::<GmAdjustForce>(por.into_bits()),
+            read::<GmAdjustForce>(por.into_bits()),
+            write::<GmAdjustForce>(released.into_bits()),
+            write::<GmAdjustForce>(released.with_force_update(ForceUpdate::Update).into_bits()),
+            read::<GmAdjustForce>(released.with_gm_adjust(0).into_bits()),
+            read::<GmAdjustForce>(released.with_gm_adjust(0).into_bits()),
+            read::<GmAdjustForce>(tuned.into_bits()),
+            read::<AutotuneRead>(
+                AutotuneRead::new()
+                    .with_autotune_a(0xb0)
+                    .with_autotune_b(0xb2)
+                    .into_bits(),
+            ),
+            write::<AutotuneForce>(0xb0b2),
+            read::<GmAdjustForce>(tuned.into_bits()),
+            write::<GmAdjustForce>(forced.into_bits()),
+            write::<GmAdjustForce>(forced.with_force_update(ForceUpdate::Update).into_bits()),
+            read::<GmAdjustForce>(forced.with_force_update(ForceUpdate::Update).into_bits()),
+            read::<GmAdjustForce>(forced.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(bq.capture_autotune().await, Ok(None));
+
+        bq.release_autotune(&mut NoopDelay).await.unwrap();
+
+        // still restarting
+        assert_eq!(bq.capture_autotune().await, Ok(None));
+
+        let autotune = bq.capture_autotune().await.unwrap().unwrap();
+        assert_eq!(
+            autotune,
+            Autotune {
+                autotune_a: 0xb0,
+                autotune_b: 0xb2,
+                gm_adjust: 0x20,
+            }
+        );
+
+        bq.apply_autotune(&mut NoopDelay, autotune).await.unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
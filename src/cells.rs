@@ -0,0 +1,187 @@
+//! Battery pack cell count and the per-cell-count defaults derived from
+//! it.
+
+use core::ops::RangeInclusive;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{AutoCharge, ChargeVoltage, VsysMin};
+use crate::{Bq2577x, Error};
+
+/// Number of series cells in the battery pack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum CellCount {
+    Two = 2,
+    Three = 3,
+    Four = 4,
+    Five = 5,
+}
+
+impl CellCount {
+    /// Lowest sane per-cell charge voltage, in millivolts.
+    const CELL_VOLTAGE_MIN_MV: u16 = 3500;
+
+    /// Highest sane per-cell charge voltage, in millivolts.
+    const CELL_VOLTAGE_MAX_MV: u16 = 4500;
+
+    /// Number of cells in series.
+    pub const fn cells(self) -> u8 {
+        self as _
+    }
+
+    /// Converts a number of cells in series.
+    pub const fn from_cells(cells: u8) -> Option<Self> {
+        match cells {
+            2 => Some(Self::Two),
+            3 => Some(Self::Three),
+            4 => Some(Self::Four),
+            5 => Some(Self::Five),
+            _ => None,
+        }
+    }
+
+    /// Infers the cell count from the POR value of [`VsysMin`], which
+    /// the charger sets according to the CELL_BATPRES pin. Returns
+    /// `None` if the register no longer holds a POR value.
+    pub const fn from_vsys_min(reg: VsysMin) -> Option<Self> {
+        match reg.voltage() {
+            0x528 => Some(Self::Two),
+            0x730 => Some(Self::Three),
+            0x99c => Some(Self::Four),
+            0xc08 => Some(Self::Five),
+            _ => None,
+        }
+    }
+
+    /// POR minimum system voltage, in millivolts.
+    pub const fn vsys_min_mv(self) -> u16 {
+        match self {
+            Self::Two => 6600,
+            Self::Three => 9200,
+            Self::Four => 12300,
+            Self::Five => 15400,
+        }
+    }
+
+    /// POR [`VsysMin`] register value.
+    pub const fn vsys_min(self) -> VsysMin {
+        VsysMin::new().with_voltage(self.vsys_min_mv() / 5)
+    }
+
+    /// POR battery recharge threshold below CHARGE_VOLTAGE(), in
+    /// millivolts.
+    pub const fn vrechg_mv(self) -> u16 {
+        match self {
+            Self::Two => 200,
+            Self::Three => 300,
+            Self::Four => 400,
+            Self::Five => 500,
+        }
+    }
+
+    /// Applies the POR recharge threshold to an [`AutoCharge`]
+    /// register value.
+    pub const fn with_vrechg(self, reg: AutoCharge) -> AutoCharge {
+        // 50mV step with a 50mV offset
+        reg.with_vrechg(self.vrechg_mv() / 50 - 1)
+    }
+
+    /// Sane charge voltage range for this pack, in millivolts, limited
+    /// to what [`ChargeVoltage`] can represent.
+    pub const fn charge_voltage_mv(self) -> RangeInclusive<u16> {
+        let cells = self.cells() as u16;
+        let min = cells * Self::CELL_VOLTAGE_MIN_MV;
+        let max = cells * Self::CELL_VOLTAGE_MAX_MV;
+
+        let min = if min < ChargeVoltage::MIN_MV {
+            ChargeVoltage::MIN_MV
+        } else {
+            min
+        };
+        let max = if max > ChargeVoltage::MAX_MV {
+            ChargeVoltage::MAX_MV
+        } else {
+            max
+        };
+
+        min..=max
+    }
+
+    /// Clamps a requested charge voltage, in millivolts, to
+    /// [`Self::charge_voltage_mv`].
+    pub const fn clamp_charge_voltage_mv(self, mv: u16) -> u16 {
+        let range = self.charge_voltage_mv();
+
+        if mv < *range.start() {
+            *range.start()
+        } else if mv > *range.end() {
+            *range.end()
+        } else {
+            mv
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Configures the pack's cell count explicitly, overriding
+    /// detection.
+    pub fn set_cell_count(&mut self, cells: CellCount) {
+        self.cells = Some(cells);
+    }
+
+    /// Returns the configured cell count or, if none was configured,
+    /// infers it from the POR value of [`VsysMin`] and remembers it.
+    pub async fn cell_count(&mut self) -> Result<Option<CellCount>, Error<IFACE::Error>> {
+        if self.cells.is_none() {
+            self.cells = CellCount::from_vsys_min(self.vsys_min().await?);
+        }
+
+        Ok(self.cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use super::*;
+    use crate::registers::Register;
+
+    #[test]
+    fn test_cell_defaults() {
+        for cells in [CellCount::Two, CellCount::Three, CellCount::Four, CellCount::Five] {
+            assert_eq!(CellCount::from_cells(cells.cells()), Some(cells));
+            assert_eq!(CellCount::from_vsys_min(cells.vsys_min()), Some(cells));
+        }
+
+        assert_eq!(CellCount::Two.vsys_min().into_bits(), VsysMin::RESET);
+        assert_eq!(CellCount::Four.vsys_min().voltage(), 0x99c);
+
+        assert_eq!(CellCount::Two.with_vrechg(AutoCharge::new()).vrechg(), 0x3);
+        assert_eq!(CellCount::Five.with_vrechg(AutoCharge::new()).vrechg(), 0x9);
+
+        assert_eq!(CellCount::Two.charge_voltage_mv(), 7000..=9000);
+        assert_eq!(CellCount::Five.charge_voltage_mv(), 17500..=22500);
+        assert_eq!(CellCount::Three.clamp_charge_voltage_mv(14000), 13500);
+        assert_eq!(CellCount::Three.clamp_charge_voltage_mv(12600), 12600);
+    }
+
+    #[tokio::test]
+    async fn test_cell_count_detection() {
+        let e = vec![Transaction::write_read(0x09, vec![VsysMin::ADDR], vec![0x07, 0x30])];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(bq.cell_count().await, Ok(Some(CellCount::Three)));
+        // detected value is cached
+        assert_eq!(bq.cell_count().await, Ok(Some(CellCount::Three)));
+
+        bq.set_cell_count(CellCount::Four);
+        assert_eq!(bq.cell_count().await, Ok(Some(CellCount::Four)));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,112 @@
+//! [`embedded_batteries_async::charger::Charger`] implementation, for
+//! generic battery management code.
+
+use embedded_batteries_async::charger::{self, Charger, ErrorKind, ErrorType, MilliAmps, MilliVolts};
+
+use crate::interface::RegisterInterface;
+use crate::registers::{ChargeCurrent, ChargeVoltage};
+use crate::{Bq2577x, Error};
+
+impl<E: core::fmt::Debug> charger::Error for Error<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Interface(_) => ErrorKind::CommError,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> ErrorType for Bq2577x<IFACE> {
+    type Error = Error<IFACE::Error>;
+}
+
+impl<IFACE: RegisterInterface> Charger for Bq2577x<IFACE> {
+    /// Sets the charge current, clamped to the charger's range for the
+    /// RSR sense resistor in use, and returns the value read back.
+    /// Setting 0 stops charging.
+    async fn charging_current(&mut self, current: MilliAmps) -> Result<MilliAmps, Self::Error> {
+        let rsr = self.charge_option1().await?.rsns_rsr();
+
+        self.set_charge_current(ChargeCurrent::new().with_milliamps(current, rsr))
+            .await?;
+
+        Ok(self.charge_current().await?.milliamps(rsr))
+    }
+
+    /// Sets the charge voltage, clamped to the charger's range, and
+    /// returns the value read back. Setting 0 stops charging.
+    async fn charging_voltage(&mut self, voltage: MilliVolts) -> Result<MilliVolts, Self::Error> {
+        self.set_charge_voltage(ChargeVoltage::new().with_millivolts(voltage))
+            .await?;
+
+        Ok(self.charge_voltage().await?.millivolts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_batteries_async::charger::Error as _;
+    use embedded_hal_async::i2c::ErrorKind as I2cErrorKind;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargeOption1, Register, RsnsRsr};
+    use crate::tests::{read, write};
+
+    #[tokio::test]
+    async fn test_charging_current() {
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET);
+        let option1_2mohm = option1.with_rsns_rsr(RsnsRsr::MilliOhms2);
+
+        // 5mΩ: 8mA steps up to 16320mA; 2mΩ: 20mA steps up to 30000mA
+        let e = vec![
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(1000 / 8).into_bits()),
+            read::<ChargeCurrent>(ChargeCurrent::new().with_current(1000 / 8).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(0x7f8).into_bits()),
+            read::<ChargeCurrent>(ChargeCurrent::new().with_current(0x7f8).into_bits()),
+            read::<ChargeOption1>(option1_2mohm.into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(0x5dc).into_bits()),
+            read::<ChargeCurrent>(ChargeCurrent::new().with_current(0x5dc).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(bq.charging_current(1005).await, Ok(1000));
+        assert_eq!(bq.charging_current(20000).await, Ok(16320));
+        assert_eq!(bq.charging_current(40000).await, Ok(30000));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_charging_voltage() {
+        let e = vec![
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12600 / 4).into_bits()),
+            read::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12600 / 4).into_bits()),
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(5000 / 4).into_bits()),
+            read::<ChargeVoltage>(ChargeVoltage::new().with_voltage(5000 / 4).into_bits()),
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(23000 / 4).into_bits()),
+            read::<ChargeVoltage>(ChargeVoltage::new().with_voltage(23000 / 4).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(bq.charging_voltage(12600).await, Ok(12600));
+        assert_eq!(bq.charging_voltage(1000).await, Ok(5000));
+        assert_eq!(bq.charging_voltage(30000).await, Ok(23000));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(Error::Interface(I2cErrorKind::Other).kind(), ErrorKind::CommError);
+        assert_eq!(Error::<I2cErrorKind>::OutOfRange.kind(), ErrorKind::Other);
+    }
+}
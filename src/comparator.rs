@@ -0,0 +1,298 @@
+//! Independent comparator on the CMPIN_TR pin, configured in terms of the
+//! monitored voltage and the resistor divider feeding the pin.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{CmpDeg, CmpEn, CmpLatch, CmpPol, Function, PPCmp, ProchotStatus, StatComp};
+use crate::{Bq2577x, Error};
+
+/// Resistor divider from the monitored voltage to CMPIN_TR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Divider {
+    /// Resistor from the monitored voltage to CMPIN_TR, in ohms.
+    pub top_ohms: u32,
+
+    /// Resistor from CMPIN_TR to ground, in ohms.
+    pub bottom_ohms: u32,
+}
+
+impl Divider {
+    /// Internal comparator threshold on CMPIN_TR, in millivolts. The
+    /// register descriptions only call it the internal threshold (see
+    /// [`crate::registers::ChargeOption1::cmp_pol`]); the value is the
+    /// typical independent comparator threshold from the datasheet's
+    /// electrical characteristics.
+    pub const REFERENCE_MV: u32 = 1200;
+
+    pub const fn new(top_ohms: u32, bottom_ohms: u32) -> Self {
+        Self { top_ohms, bottom_ohms }
+    }
+
+    /// Picks the top resistor that makes the comparator trip at
+    /// `threshold_mv` on the monitored voltage, for a given bottom
+    /// resistor. Returns `None` if the threshold is below the internal
+    /// reference.
+    pub const fn for_threshold(threshold_mv: u32, bottom_ohms: u32) -> Option<Self> {
+        if threshold_mv < Self::REFERENCE_MV {
+            return None;
+        }
+
+        let top_ohms =
+            (bottom_ohms as u64 * (threshold_mv - Self::REFERENCE_MV) as u64 / Self::REFERENCE_MV as u64) as u32;
+
+        Some(Self::new(top_ohms, bottom_ohms))
+    }
+
+    /// Monitored voltage at which the comparator trips, in millivolts.
+    pub const fn threshold_mv(&self) -> u32 {
+        self.input_mv(Self::REFERENCE_MV)
+    }
+
+    /// Converts a CMPIN_TR pin voltage to the monitored voltage, in
+    /// millivolts.
+    pub const fn input_mv(&self, cmpin_mv: u32) -> u32 {
+        if self.bottom_ohms == 0 {
+            return 0;
+        }
+
+        (cmpin_mv as u64 * (self.top_ohms as u64 + self.bottom_ohms as u64) / self.bottom_ohms as u64) as u32
+    }
+}
+
+/// Independent comparator configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ComparatorConfig {
+    /// Divider feeding CMPIN_TR, which sets the trip point on the
+    /// monitored voltage.
+    pub divider: Divider,
+
+    /// Output polarity, which also selects internal or external
+    /// hysteresis.
+    pub polarity: CmpPol,
+
+    /// Deglitch time on the falling edge of CMPOUT.
+    pub deglitch: CmpDeg,
+
+    /// Keep CMPOUT low after a trip until the latch is toggled.
+    pub latch: bool,
+
+    /// Assert PROCHOT and report the trip in
+    /// [`crate::registers::ProchotStatus::stat_comp_`].
+    pub prochot: bool,
+}
+
+impl ComparatorConfig {
+    /// Configuration with internal hysteresis, the shortest deglitch,
+    /// no latch and PROCHOT reporting.
+    pub const fn new(divider: Divider) -> Self {
+        Self {
+            divider,
+            polarity: CmpPol::ActiveLow,
+            deglitch: CmpDeg::Time1us,
+            latch: false,
+            prochot: true,
+        }
+    }
+
+    /// Configuration tripping at `threshold_mv` on the monitored
+    /// voltage with a `bottom_ohms` resistor to ground, or `None` if the
+    /// threshold is below [`Divider::REFERENCE_MV`].
+    pub const fn for_threshold(threshold_mv: u32, bottom_ohms: u32) -> Option<Self> {
+        match Divider::for_threshold(threshold_mv, bottom_ohms) {
+            Some(divider) => Some(Self::new(divider)),
+            None => None,
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Routes CMPIN_TR to the comparator, applies `config` and enables
+    /// the comparator. Returns the monitored voltage at which the
+    /// comparator trips, in millivolts, or fails with
+    /// [`Error::OutOfRange`] if the divider has no bottom resistor or
+    /// its trip point is below [`Divider::REFERENCE_MV`].
+    pub async fn configure_comparator(&mut self, config: ComparatorConfig) -> Result<u32, Error<IFACE::Error>> {
+        let threshold_mv = config.divider.threshold_mv();
+
+        if config.divider.bottom_ohms == 0 || threshold_mv < Divider::REFERENCE_MV {
+            return Err(Error::OutOfRange);
+        }
+
+        let option5 = self.charge_option5().await?;
+        self.set_charge_option5(option5.with_cmpin_tr_select(Function::Cmpin))
+            .await?;
+
+        let option1 = self.charge_option1().await?;
+        self.set_charge_option1(option1.with_cmp_pol(config.polarity).with_cmp_deg(config.deglitch))
+            .await?;
+
+        let latch = if config.latch {
+            CmpLatch::Latch
+        } else {
+            CmpLatch::NoLatch
+        };
+
+        let option0 = self.charge_option0().await?;
+        self.set_charge_option0(option0.with_en_cmp_latch(latch)).await?;
+
+        let pp_cmp = if config.prochot { PPCmp::Enable } else { PPCmp::Disable };
+
+        let prochot1 = self.prochot_option1().await?;
+        self.set_prochot_option1(prochot1.with_pp_cmp(pp_cmp)).await?;
+
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_cmp_en(CmpEn::Enable)).await?;
+
+        Ok(threshold_mv)
+    }
+
+    /// Disables the comparator.
+    pub async fn disable_comparator(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_cmp_en(CmpEn::Disable)).await
+    }
+
+    /// Releases a latched comparator output by toggling the latch
+    /// enable.
+    pub async fn clear_comparator_latch(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option0 = self.charge_option0().await?;
+        self.set_charge_option0(option0.with_en_cmp_latch(CmpLatch::NoLatch))
+            .await?;
+        self.set_charge_option0(option0).await
+    }
+
+    /// Reads the monitored voltage from the CMPIN ADC channel, in
+    /// millivolts.
+    pub async fn comparator_input_mv(&mut self, divider: Divider) -> Result<u32, Error<IFACE::Error>> {
+        let cmpin = self.adc_cmpin_tr().await?.voltage();
+
+        Ok(divider.input_mv(cmpin as u32))
+    }
+
+    /// Waits for the comparator to trip, polling every `poll_interval_ms`
+    /// and failing with [`Error::Timeout`] after `max_polls` polls.
+    ///
+    /// The trip is only reported with [`ComparatorConfig::prochot`]
+    /// set. A trip that happened before the call is reported
+    /// immediately, since the status is latched until read.
+    pub async fn wait_comparator_trip<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_ms: u32,
+        max_polls: u32,
+    ) -> Result<(), Error<IFACE::Error>> {
+        for _ in 0..max_polls {
+            let comp = ProchotStatus::new().with_stat_comp_(StatComp::Triggered);
+
+            if self.prochot_take(comp).await?.stat_comp_() == StatComp::Triggered {
+                return Ok(());
+            }
+
+            delay.delay_ms(poll_interval_ms).await;
+        }
+
+        Err(Error::Timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{
+        AdcCmpinTr, ChargeOption0, ChargeOption1, ChargeOption3, ChargeOption5, ProchotOption1, ProchotStatus, Register,
+    };
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_divider() {
+        // 4.8V trip point with 10k to ground
+        let divider = Divider::for_threshold(4800, 10_000).unwrap();
+        assert_eq!(divider, Divider::new(30_000, 10_000));
+        assert_eq!(divider.threshold_mv(), 4800);
+        assert_eq!(divider.input_mv(1000), 4000);
+
+        assert_eq!(Divider::for_threshold(1000, 10_000), None);
+        assert_eq!(Divider::new(10_000, 0).input_mv(1000), 0);
+    }
+
+    #[tokio::test]
+    async fn test_configure_comparator() {
+        let option5 = ChargeOption5::from_bits(ChargeOption5::RESET);
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET);
+        let option0 = ChargeOption0::from_bits(ChargeOption0::RESET);
+        let prochot1 = ProchotOption1::from_bits(ProchotOption1::RESET);
+        let option3 = ChargeOption3::from_bits(ChargeOption3::RESET);
+
+        let e = vec![
+            read::<ChargeOption5>(option5.with_cmpin_tr_select(Function::Treg).into_bits()),
+            write::<ChargeOption5>(option5.with_cmpin_tr_select(Function::Cmpin).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeOption1>(
+                option1
+                    .with_cmp_pol(CmpPol::ActiveHigh)
+                    .with_cmp_deg(CmpDeg::Time20850us)
+                    .into_bits(),
+            ),
+            read::<ChargeOption0>(option0.into_bits()),
+            write::<ChargeOption0>(option0.with_en_cmp_latch(CmpLatch::Latch).into_bits()),
+            read::<ProchotOption1>(prochot1.into_bits()),
+            write::<ProchotOption1>(prochot1.with_pp_cmp(PPCmp::Enable).into_bits()),
+            read::<ChargeOption3>(option3.into_bits()),
+            write::<ChargeOption3>(option3.with_cmp_en(CmpEn::Enable).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = ComparatorConfig {
+            polarity: CmpPol::ActiveHigh,
+            deglitch: CmpDeg::Time20850us,
+            latch: true,
+            ..ComparatorConfig::for_threshold(4800, 10_000).unwrap()
+        };
+
+        assert_eq!(bq.configure_comparator(config).await, Ok(4800));
+
+        assert_eq!(ComparatorConfig::for_threshold(1000, 10_000), None);
+        assert_eq!(
+            bq.configure_comparator(ComparatorConfig::new(Divider::new(10_000, 0)))
+                .await,
+            Err(Error::OutOfRange)
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_wait_comparator_trip() {
+        let idle = ProchotStatus::from_bits(ProchotStatus::RESET);
+        let tripped = idle.with_stat_comp_(StatComp::Triggered);
+
+        let e = vec![
+            read::<AdcCmpinTr>(1200),
+            read::<ProchotStatus>(idle.into_bits()),
+            read::<ProchotStatus>(tripped.into_bits()),
+            read::<ProchotStatus>(idle.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(bq.comparator_input_mv(Divider::new(30_000, 10_000)).await, Ok(4800));
+        bq.wait_comparator_trip(&mut NoopDelay, 10, 2).await.unwrap();
+        assert_eq!(
+            bq.wait_comparator_trip(&mut NoopDelay, 10, 1).await,
+            Err(Error::Timeout)
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,342 @@
+//! Software coulomb counting for systems without a fuel gauge: charge
+//! and energy totals integrated from periodic ADC samples.
+
+use crate::interface::RegisterInterface;
+use crate::power_path::VBUS_PRESENT_MV;
+use crate::{Bq2577x, Error};
+
+/// Milliseconds per hour.
+const MS_PER_HOUR: u64 = 3_600_000;
+
+/// One reading of the battery and input ADC channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerSample {
+    /// Caller's time of the sample, in milliseconds.
+    pub timestamp_ms: u64,
+
+    /// Battery current in milliamps; positive when charging, negative
+    /// when discharging. The charger only measures discharge current in
+    /// battery only or OTG mode, so with an adapter present, such as in
+    /// supplement mode, a discharge reads 0; see
+    /// [`Self::discharge_measured`].
+    pub ibat_ma: i32,
+
+    /// Battery voltage in millivolts.
+    pub vbat_mv: i32,
+
+    /// Input current in milliamps; positive from the adapter, negative
+    /// towards VBUS in OTG mode.
+    pub iin_ma: i32,
+
+    /// Input voltage in millivolts.
+    pub vbus_mv: i32,
+}
+
+impl PowerSample {
+    /// Whether a battery discharge would show in [`Self::ibat_ma`]. It
+    /// doesn't while the adapter is present and delivering current,
+    /// unless the battery is charging.
+    pub const fn discharge_measured(&self) -> bool {
+        self.ibat_ma > 0 || self.vbus_mv <= VBUS_PRESENT_MV || self.iin_ma <= 0
+    }
+}
+
+/// Charge and energy totals. Each sample's readings are held until the
+/// next sample, like the charger's own ADC readings between conversions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CoulombCounter {
+    /// Charge into the battery, in mA·ms.
+    charge_in: u64,
+
+    /// Charge out of the battery, in mA·ms.
+    charge_out: u64,
+
+    /// Energy into the battery, in µW·ms.
+    energy_in: u64,
+
+    /// Energy out of the battery, in µW·ms.
+    energy_out: u64,
+
+    /// Energy from the adapter, in µW·ms.
+    input_energy_in: u64,
+
+    /// Energy out to VBUS in OTG mode, in µW·ms.
+    input_energy_out: u64,
+
+    /// Time during which battery discharge wasn't measured, in ms.
+    unmeasured: u64,
+
+    last: Option<PowerSample>,
+}
+
+impl CoulombCounter {
+    /// Length of the serialized form, in bytes.
+    pub const LEN: usize = 56;
+
+    pub const fn new() -> Self {
+        Self {
+            charge_in: 0,
+            charge_out: 0,
+            energy_in: 0,
+            energy_out: 0,
+            input_energy_in: 0,
+            input_energy_out: 0,
+            unmeasured: 0,
+            last: None,
+        }
+    }
+
+    /// Adds the interval since the previous sample, then holds
+    /// `sample` until the next one. The first sample, and a sample
+    /// older than the previous one, only start a new interval.
+    pub fn update(&mut self, sample: PowerSample) {
+        if let Some(last) = self.last {
+            if let Some(dt) = sample.timestamp_ms.checked_sub(last.timestamp_ms) {
+                self.integrate(&last, dt);
+            }
+        }
+
+        self.last = Some(sample);
+    }
+
+    fn integrate(&mut self, sample: &PowerSample, dt: u64) {
+        if !sample.discharge_measured() {
+            self.unmeasured += dt;
+        }
+
+        let charge = sample.ibat_ma.unsigned_abs() as u64 * dt;
+        let energy = charge * sample.vbat_mv.max(0) as u64;
+
+        if sample.ibat_ma > 0 {
+            self.charge_in += charge;
+            self.energy_in += energy;
+        } else {
+            self.charge_out += charge;
+            self.energy_out += energy;
+        }
+
+        let input_energy = sample.iin_ma.unsigned_abs() as u64 * dt * sample.vbus_mv.max(0) as u64;
+
+        if sample.iin_ma > 0 {
+            self.input_energy_in += input_energy;
+        } else {
+            self.input_energy_out += input_energy;
+        }
+    }
+
+    /// Charge into the battery, in milliamp-hours.
+    pub const fn charge_in_mah(&self) -> u32 {
+        (self.charge_in / MS_PER_HOUR) as u32
+    }
+
+    /// Charge out of the battery, in milliamp-hours.
+    pub const fn charge_out_mah(&self) -> u32 {
+        (self.charge_out / MS_PER_HOUR) as u32
+    }
+
+    /// Energy into the battery, in milliwatt-hours.
+    pub const fn energy_in_mwh(&self) -> u32 {
+        (self.energy_in / MS_PER_HOUR / 1000) as u32
+    }
+
+    /// Energy out of the battery, in milliwatt-hours.
+    pub const fn energy_out_mwh(&self) -> u32 {
+        (self.energy_out / MS_PER_HOUR / 1000) as u32
+    }
+
+    /// Energy drawn from the adapter, in milliwatt-hours.
+    pub const fn input_energy_in_mwh(&self) -> u32 {
+        (self.input_energy_in / MS_PER_HOUR / 1000) as u32
+    }
+
+    /// Energy supplied to VBUS in OTG mode, in milliwatt-hours.
+    pub const fn input_energy_out_mwh(&self) -> u32 {
+        (self.input_energy_out / MS_PER_HOUR / 1000) as u32
+    }
+
+    /// Time during which the adapter was present and a battery
+    /// discharge wouldn't have been measured, in milliseconds. Any
+    /// discharge in that time, such as in supplement mode, is missing
+    /// from the charge and energy out totals.
+    pub const fn unmeasured_discharge_ms(&self) -> u64 {
+        self.unmeasured
+    }
+
+    /// Net charge into the battery, in milliamp-hours.
+    pub const fn net_charge_mah(&self) -> i64 {
+        (self.charge_in as i64 - self.charge_out as i64) / MS_PER_HOUR as i64
+    }
+
+    /// Serializes the totals, keeping their full resolution. The held
+    /// sample isn't saved, as timestamps don't carry over a restart.
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0; Self::LEN];
+
+        let totals = [
+            self.charge_in,
+            self.charge_out,
+            self.energy_in,
+            self.energy_out,
+            self.input_energy_in,
+            self.input_energy_out,
+            self.unmeasured,
+        ];
+
+        for (chunk, total) in bytes.chunks_exact_mut(8).zip(totals) {
+            chunk.copy_from_slice(&total.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Restores totals saved by [`Self::to_bytes`]. Counting resumes
+    /// with the next sample.
+    pub fn from_bytes(bytes: [u8; Self::LEN]) -> Self {
+        let mut totals = [0; 7];
+
+        for (total, chunk) in totals.iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut le = [0; 8];
+            le.copy_from_slice(chunk);
+            *total = u64::from_le_bytes(le);
+        }
+
+        let [charge_in, charge_out, energy_in, energy_out, input_energy_in, input_energy_out, unmeasured] = totals;
+
+        Self {
+            charge_in,
+            charge_out,
+            energy_in,
+            energy_out,
+            input_energy_in,
+            input_energy_out,
+            unmeasured,
+            last: None,
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Reads the battery and input ADC channels, scaled for the sense
+    /// resistors in use. The ADC must be converting the IBAT, VBAT, IIN
+    /// and VBUS channels, or the readings are stale.
+    pub async fn power_sample(&mut self, timestamp_ms: u64) -> Result<PowerSample, Error<IFACE::Error>> {
+        let option1 = self.charge_option1().await?;
+
+        Ok(PowerSample {
+            timestamp_ms,
+            ibat_ma: self.adc_ibat().await?.milliamps(option1.rsns_rsr()),
+            vbat_mv: self.adc_vbat().await?.millivolts(),
+            iin_ma: self.adc_iin().await?.milliamps(option1.rsns_rac()),
+            vbus_mv: self.adc_vbus().await?.millivolts(),
+        })
+    }
+
+    /// Takes a [`Self::power_sample`] and adds it to `counter`. Call
+    /// periodically.
+    pub async fn update_coulomb_counter(
+        &mut self,
+        counter: &mut CoulombCounter,
+        timestamp_ms: u64,
+    ) -> Result<PowerSample, Error<IFACE::Error>> {
+        let sample = self.power_sample(timestamp_ms).await?;
+        counter.update(sample);
+
+        Ok(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{AdcIbat, AdcIin, AdcVbat, AdcVbus, ChargeOption1, Register, RsnsRac};
+    use crate::tests::read;
+
+    fn sample(timestamp_ms: u64, ibat_ma: i32, iin_ma: i32) -> PowerSample {
+        PowerSample {
+            timestamp_ms,
+            ibat_ma,
+            vbat_mv: 12000,
+            iin_ma,
+            vbus_mv: 20000,
+        }
+    }
+
+    #[test]
+    fn test_coulomb_counter() {
+        let mut counter = CoulombCounter::new();
+
+        // 2A charging from a 3A adapter for an hour, then 1A discharging
+        // for half an hour
+        counter.update(sample(1000, 2000, 3000));
+        counter.update(sample(3_601_000, -1000, 0));
+        counter.update(sample(5_401_000, 0, 0));
+
+        assert_eq!(counter.charge_in_mah(), 2000);
+        assert_eq!(counter.charge_out_mah(), 500);
+        assert_eq!(counter.net_charge_mah(), 1500);
+        assert_eq!(counter.energy_in_mwh(), 24000);
+        assert_eq!(counter.energy_out_mwh(), 6000);
+        assert_eq!(counter.input_energy_in_mwh(), 60000);
+        assert_eq!(counter.input_energy_out_mwh(), 0);
+        assert_eq!(counter.unmeasured_discharge_ms(), 0);
+
+        // adapter present and no battery current: a supplement mode
+        // discharge wouldn't show
+        counter.update(sample(5_402_000, 0, 3000));
+        counter.update(sample(5_412_000, 1000, 3000));
+        assert_eq!(counter.unmeasured_discharge_ms(), 10_000);
+
+        // a timestamp going backwards only restarts the interval
+        counter.update(sample(0, 5000, 0));
+        assert_eq!(counter.charge_in_mah(), 2000);
+
+        let mut restored = CoulombCounter::from_bytes(counter.to_bytes());
+        assert_eq!(restored.charge_in_mah(), 2000);
+        // plus 60W for 10s
+        assert_eq!(restored.input_energy_in_mwh(), 60166);
+        assert_eq!(restored.unmeasured_discharge_ms(), 10_000);
+
+        restored.update(sample(0, 0, -500));
+        restored.update(sample(3_600_000, 0, 0));
+        assert_eq!(restored.input_energy_out_mwh(), 10000);
+        assert_eq!(restored.charge_in_mah(), 2000);
+    }
+
+    #[tokio::test]
+    async fn test_power_sample() {
+        assert_eq!(AdcIin::new().with_current(2000).milliamps(RsnsRac::MilliOhms10), 1000);
+        assert_eq!(AdcIin::new().with_current(-2000).milliamps(RsnsRac::MilliOhms5), -2000);
+
+        // POR RSR is 5mΩ and RAC 10mΩ
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<AdcIbat>(AdcIbat::new().with_current(-1500).into_bits()),
+            read::<AdcVbat>(AdcVbat::new().with_voltage(11800).into_bits()),
+            read::<AdcIin>(AdcIin::new().with_current(0).into_bits()),
+            read::<AdcVbus>(AdcVbus::new().with_voltage(0).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let mut counter = CoulombCounter::new();
+
+        assert_eq!(
+            bq.update_coulomb_counter(&mut counter, 10).await,
+            Ok(PowerSample {
+                timestamp_ms: 10,
+                ibat_ma: -1500,
+                vbat_mv: 11800,
+                iin_ma: 0,
+                vbus_mv: 0,
+            })
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
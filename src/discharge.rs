@@ -0,0 +1,286 @@
+//! Battery discharge current protection: two PROCHOT levels on the
+//! current through the RSR sense resistor, in milliamps instead of codes
+//! scaled by the sense resistor.
+
+use crate::interface::RegisterInterface;
+use crate::registers::{
+    ChargeOption4, EnIchgIdchg, Idchg2Threshold, IdchgDeg1, IdchgDeg2, PPIdchg1, PPIdchg2, ProchotOption1,
+    ProchotStatus, StatIdchg1, StatIdchg2,
+};
+use crate::{Bq2577x, Error};
+
+/// Discharge current protection thresholds. Thresholds and deglitch
+/// times that don't match a charger setting are rounded down to the
+/// nearest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DischargeProtection {
+    /// Level 1 threshold in milliamps: 2000mA to 33000mA in 500mA steps
+    /// with a 5mΩ RSR sense resistor, or 2.5 times that with 2mΩ.
+    pub level1_ma: u32,
+
+    /// Time the current must stay above level 1 before PROCHOT, in
+    /// milliseconds.
+    pub level1_deglitch_ms: u32,
+
+    /// Level 2 threshold in milliamps, 125% to 400% of the level 1
+    /// threshold in effect, or `None` to disable level 2.
+    pub level2_ma: Option<u32>,
+
+    /// Time the current must stay above level 2 before PROCHOT, in
+    /// microseconds.
+    pub level2_deglitch_us: u32,
+
+    /// Current reported on the IBAT pin.
+    pub ibat_pin: EnIchgIdchg,
+}
+
+/// Discharge protection level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DischargeLevel {
+    /// Sustained discharge current above the level 1 threshold.
+    Level1,
+
+    /// Discharge current peak above the level 2 threshold.
+    Level2,
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Programs the discharge current thresholds and enables their
+    /// PROCHOT profiles. Fails with [`Error::OutOfRange`] if a threshold
+    /// or deglitch time is below the charger's lowest one.
+    pub async fn configure_discharge_protection(
+        &mut self,
+        config: DischargeProtection,
+    ) -> Result<(), Error<IFACE::Error>> {
+        let deg1 = IdchgDeg1::from_millis(config.level1_deglitch_ms).ok_or(Error::OutOfRange)?;
+        let deg2 = IdchgDeg2::from_micros(config.level2_deglitch_us).ok_or(Error::OutOfRange)?;
+
+        let rsr = self.charge_option1().await?.rsns_rsr();
+
+        // the lowest code keeps PROCHOT always triggered
+        if config.level1_ma < ProchotOption1::idchg_th1_min_ma(rsr) {
+            return Err(Error::OutOfRange);
+        }
+
+        let option1 = self.prochot_option1().await?.with_idchg_th1_ma(config.level1_ma, rsr);
+        let level1_ma = option1.idchg_th1_ma(rsr);
+
+        if config.level1_ma < level1_ma {
+            return Err(Error::OutOfRange);
+        }
+
+        let level2 = match config.level2_ma {
+            Some(ma) => {
+                let percent = (ma.saturating_mul(100) / level1_ma).min(u16::MAX as u32) as u16;
+
+                Some(Idchg2Threshold::from_percent(percent).ok_or(Error::OutOfRange)?)
+            }
+            None => None,
+        };
+
+        self.set_prochot_option1(option1.with_idchg_deg1(deg1).with_pp_idchg1(PPIdchg1::Enable))
+            .await?;
+
+        let option4 = self.option4_read().await?.with_idchg_deg2(deg2);
+
+        let option4 = match level2 {
+            Some(threshold) => option4.with_idchg_th2(threshold).with_pp_idchg2(PPIdchg2::Enable),
+            None => option4.with_pp_idchg2(PPIdchg2::Disable),
+        };
+
+        self.set_charge_option4(option4).await?;
+
+        let option2 = self.charge_option2().await?;
+        self.set_charge_option2(option2.with_en_ichg_idchg(config.ibat_pin))
+            .await
+    }
+
+    /// Disables both discharge current PROCHOT profiles.
+    pub async fn disable_discharge_protection(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option1 = self.prochot_option1().await?;
+        self.set_prochot_option1(option1.with_pp_idchg1(PPIdchg1::Disable))
+            .await?;
+
+        let option4 = self.option4_read().await?;
+        self.set_charge_option4(option4.with_pp_idchg2(PPIdchg2::Disable)).await
+    }
+
+    /// Reports the highest discharge level that tripped since the last
+    /// call, or `None`. Both statuses are latched until read, so this
+    /// also clears them.
+    pub async fn discharge_trip(&mut self) -> Result<Option<DischargeLevel>, Error<IFACE::Error>> {
+        let idchg1 = ProchotStatus::new().with_stat_idchg1_(StatIdchg1::Triggered);
+        let level1 = self.prochot_take(idchg1).await?.stat_idchg1_() == StatIdchg1::Triggered;
+        let idchg2 = ChargeOption4::new().with_stat_idchg2(StatIdchg2::Triggered);
+        let level2 = self.option4_take(idchg2).await?.stat_idchg2() == StatIdchg2::Triggered;
+
+        Ok(if level2 {
+            Some(DischargeLevel::Level2)
+        } else if level1 {
+            Some(DischargeLevel::Level1)
+        } else {
+            None
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargeOption1, ChargeOption2, Register, RsnsRsr, StatVbusVap};
+    use crate::tests::{read, write};
+    use crate::vap::VapOutcome;
+
+    #[test]
+    fn test_discharge_units() {
+        let option1 = ProchotOption1::from_bits(ProchotOption1::RESET);
+        assert_eq!(option1.idchg_th1_ma(RsnsRsr::MilliOhms5), 9500);
+        assert_eq!(option1.idchg_th1_ma(RsnsRsr::MilliOhms2), 23750);
+        assert_eq!(option1.with_idchg_th1_ma(10_400, RsnsRsr::MilliOhms5).idchg_th1(), 0x11);
+        assert_eq!(option1.with_idchg_th1_ma(0, RsnsRsr::MilliOhms5).idchg_th1(), 0);
+        assert_eq!(ProchotOption1::idchg_th1_min_ma(RsnsRsr::MilliOhms5), 2000);
+        assert_eq!(ProchotOption1::idchg_th1_min_ma(RsnsRsr::MilliOhms2), 5000);
+        assert_eq!(
+            option1
+                .with_idchg_th1_ma(100_000, RsnsRsr::MilliOhms2)
+                .idchg_th1_ma(RsnsRsr::MilliOhms2),
+            82500
+        );
+
+        assert_eq!(Idchg2Threshold::from_percent(100), None);
+        assert_eq!(Idchg2Threshold::from_percent(180), Some(Idchg2Threshold::Threshold175));
+        assert_eq!(IdchgDeg1::from_millis(50), None);
+        assert_eq!(IdchgDeg1::from_millis(6000), Some(IdchgDeg1::Time5000ms));
+        assert_eq!(IdchgDeg2::from_micros(50), None);
+        assert_eq!(IdchgDeg2::from_micros(2000), Some(IdchgDeg2::Time1550us));
+    }
+
+    #[tokio::test]
+    async fn test_configure_discharge_protection() {
+        let option1 = ProchotOption1::from_bits(ProchotOption1::RESET);
+        let option4 = ChargeOption4::from_bits(ChargeOption4::RESET);
+        let option2 = ChargeOption2::from_bits(ChargeOption2::RESET);
+        let status = ProchotStatus::from_bits(ProchotStatus::RESET);
+
+        // POR RSR is 5mΩ: 10000mA is code 0x11, 20000mA is 200% of it
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ProchotOption1>(option1.into_bits()),
+            write::<ProchotOption1>(
+                option1
+                    .with_idchg_th1(0x11)
+                    .with_idchg_deg1(IdchgDeg1::Time78ms)
+                    .with_pp_idchg1(PPIdchg1::Enable)
+                    .into_bits(),
+            ),
+            read::<ChargeOption4>(option4.into_bits()),
+            write::<ChargeOption4>(
+                option4
+                    .with_idchg_deg2(IdchgDeg2::Time98us)
+                    .with_idchg_th2(Idchg2Threshold::Threshold200)
+                    .with_pp_idchg2(PPIdchg2::Enable)
+                    .into_bits(),
+            ),
+            read::<ChargeOption2>(option2.into_bits()),
+            write::<ChargeOption2>(option2.into_bits()),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ProchotOption1>(option1.into_bits()),
+            read::<ProchotStatus>(status.with_stat_idchg1_(StatIdchg1::Triggered).into_bits()),
+            read::<ChargeOption4>(option4.with_stat_idchg2(StatIdchg2::Triggered).into_bits()),
+            read::<ProchotStatus>(status.with_stat_idchg1_(StatIdchg1::Triggered).into_bits()),
+            read::<ChargeOption4>(option4.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = DischargeProtection {
+            level1_ma: 10_000,
+            level1_deglitch_ms: 78,
+            level2_ma: Some(20_000),
+            level2_deglitch_us: 98,
+            ibat_pin: EnIchgIdchg::Discharge,
+        };
+
+        bq.configure_discharge_protection(config).await.unwrap();
+
+        let config = DischargeProtection {
+            level2_ma: Some(11_000),
+            ..config
+        };
+
+        assert_eq!(bq.configure_discharge_protection(config).await, Err(Error::OutOfRange));
+
+        assert_eq!(bq.discharge_trip().await, Ok(Some(DischargeLevel::Level2)));
+        assert_eq!(bq.discharge_trip().await, Ok(Some(DischargeLevel::Level1)));
+        assert_eq!(bq.discharge_trip().await, Ok(None));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_discharge_level1_min() {
+        // POR RSR is 5mΩ, where 1500mA to 1999mA would encode to code 0
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = DischargeProtection {
+            level1_ma: 1500,
+            level1_deglitch_ms: 78,
+            level2_ma: None,
+            level2_deglitch_us: 98,
+            ibat_pin: EnIchgIdchg::Discharge,
+        };
+
+        assert_eq!(bq.configure_discharge_protection(config).await, Err(Error::OutOfRange));
+
+        let config = DischargeProtection {
+            level1_ma: 1999,
+            ..config
+        };
+
+        assert_eq!(bq.configure_discharge_protection(config).await, Err(Error::OutOfRange));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_discharge_trip_with_vap() {
+        let option4 = ChargeOption4::from_bits(ChargeOption4::RESET);
+        let status = ProchotStatus::from_bits(ProchotStatus::RESET);
+
+        let e = vec![
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.with_stat_idchg2(StatIdchg2::Triggered).into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.with_stat_vbus_vap(StatVbusVap::Triggered).into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        // each read clears both events in the charger, but each is still
+        // reported to the feature watching it
+        assert_eq!(bq.vap_status().await, Ok(VapOutcome::Active));
+        assert_eq!(bq.discharge_trip().await, Ok(Some(DischargeLevel::Level2)));
+        assert_eq!(bq.vap_status().await, Ok(VapOutcome::VbusLow));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,255 @@
+//! USB-C Fast Role Swap (FRS): taking over as VBUS source within
+//! microseconds of the port partner dropping power.
+//!
+//! The Type-C or PD controller drives the EN_OTG pin high on the FRS
+//! signal; with OTG pre-armed, the charger then starts sourcing VBUS
+//! without waiting for the host.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{EnOtg, FastRoleSwap, OtgCurrent, OtgVapMode, OtgVoltage, ProchotStatus, StatAdapterRemoval};
+use crate::{Bq2577x, Error};
+
+/// VBUS output after a swap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrsConfig {
+    /// VBUS voltage, 3000mV to 5000mV.
+    pub voltage_mv: u16,
+
+    /// VBUS current limit, 100mA to 3000mA with a 10mΩ RAC sense
+    /// resistor, or twice that with 5mΩ.
+    pub current_ma: u16,
+}
+
+impl Default for FrsConfig {
+    /// vSafe5V at 3A, the most a Type-C source may offer without PD.
+    fn default() -> Self {
+        Self {
+            voltage_mv: 5000,
+            current_ma: 3000,
+        }
+    }
+}
+
+/// Fast Role Swap state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FrsStatus {
+    /// FRS isn't enabled.
+    Disarmed,
+
+    /// FRS is enabled and the partner still supplies VBUS.
+    Armed,
+
+    /// The partner dropped VBUS while armed, so the charger took over
+    /// sourcing it.
+    Swapped,
+
+    /// The adapter was removed while armed, but the charger isn't
+    /// sourcing VBUS, so the cable was unplugged rather than swapped.
+    Removed,
+}
+
+const ADAPTER_REMOVAL: ProchotStatus = ProchotStatus::new().with_stat_adapter_removal(StatAdapterRemoval::Triggered);
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Programs the OTG output for `config`, hands the EN_OTG pin to OTG
+    /// mode and enables FRS. Fails with [`Error::OutOfRange`] if the
+    /// voltage or current is outside the register range.
+    pub async fn arm_frs(&mut self, config: FrsConfig) -> Result<(), Error<IFACE::Error>> {
+        if !(OtgVoltage::MIN_MV..=OtgVoltage::MAX_MV).contains(&config.voltage_mv) {
+            return Err(Error::OutOfRange);
+        }
+
+        let rac = self.charge_option1().await?.rsns_rac();
+
+        if !(OtgCurrent::min_milliamps(rac)..=OtgCurrent::max_milliamps(rac)).contains(&config.current_ma) {
+            return Err(Error::OutOfRange);
+        }
+
+        self.set_otg_voltage(OtgVoltage::new().with_millivolts(config.voltage_mv))
+            .await?;
+        self.set_otg_current(OtgCurrent::new().with_milliamps(config.current_ma, rac))
+            .await?;
+
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(
+            option3
+                .with_otg_vap_mode(OtgVapMode::OtgMode)
+                .with_en_otg(EnOtg::Enable),
+        )
+        .await?;
+
+        // clear a stale adapter removal so it isn't taken for a swap
+        self.prochot_take(ADAPTER_REMOVAL).await?;
+
+        let vap = self.vmin_active_protection().await?;
+        self.set_vmin_active_protection(vap.with_en_frs(FastRoleSwap::Enable))
+            .await
+    }
+
+    /// Disables FRS and OTG, so the charger stops sourcing VBUS if a
+    /// swap occurred.
+    pub async fn disarm_frs(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let vap = self.vmin_active_protection().await?;
+        self.set_vmin_active_protection(vap.with_en_frs(FastRoleSwap::Disable))
+            .await?;
+
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_en_otg(EnOtg::Disable)).await
+    }
+
+    /// Reports the FRS state. The adapter removal status is latched, so
+    /// each removal is reported once, as [`FrsStatus::Swapped`] or
+    /// [`FrsStatus::Removed`].
+    ///
+    /// Any unplug sets the removal status, so a swap is only reported
+    /// if OTG is enabled and VBUS is still present, measured as in
+    /// [`Self::measure_adapter_present`].
+    pub async fn frs_status<D: DelayNs>(&mut self, delay: &mut D) -> Result<FrsStatus, Error<IFACE::Error>> {
+        if self.vmin_active_protection().await?.en_frs() == FastRoleSwap::Disable {
+            return Ok(FrsStatus::Disarmed);
+        }
+
+        let status = self.prochot_take(ADAPTER_REMOVAL).await?;
+
+        if status.stat_adapter_removal() != StatAdapterRemoval::Triggered {
+            return Ok(FrsStatus::Armed);
+        }
+
+        if self.charge_option3().await?.en_otg() == EnOtg::Enable && self.measure_adapter_present(delay).await? {
+            Ok(FrsStatus::Swapped)
+        } else {
+            Ok(FrsStatus::Removed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{
+        AdcEn, AdcOption, AdcRate, AdcVbus, ChargeOption1, ChargeOption3, EnAdcVbus, Register, RsnsRac, StatComp,
+        VminActiveProtection,
+    };
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_otg_units() {
+        assert_eq!(OtgVoltage::new().with_millivolts(5000).into_bits(), OtgVoltage::RESET);
+        assert_eq!(OtgVoltage::new().with_millivolts(1000).millivolts(), 3000);
+
+        let otg = OtgCurrent::new().with_milliamps(3000, RsnsRac::MilliOhms10);
+        assert_eq!(otg.into_bits(), OtgCurrent::RESET);
+        assert_eq!(otg.milliamps(RsnsRac::MilliOhms5), 6000);
+        assert_eq!(OtgCurrent::min_milliamps(RsnsRac::MilliOhms5), 200);
+        assert_eq!(OtgCurrent::new().with_milliamps(0, RsnsRac::MilliOhms10).current(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_frs() {
+        let option3 = ChargeOption3::from_bits(ChargeOption3::RESET);
+        let armed = option3.with_en_otg(EnOtg::Enable);
+        let vap = VminActiveProtection::from_bits(VminActiveProtection::RESET);
+        let enabled = vap.with_en_frs(FastRoleSwap::Enable);
+        let status = ProchotStatus::from_bits(ProchotStatus::RESET);
+        let removed = status.with_stat_adapter_removal(StatAdapterRemoval::Triggered);
+        let adc_option = AdcOption::from_bits(AdcOption::RESET)
+            .with_en_adc_vbus(EnAdcVbus::Enable)
+            .with_adc_rate(AdcRate::Continuous)
+            .with_adc_en(AdcEn::Enable);
+
+        // POR RAC is 10mΩ
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            write::<OtgVoltage>(OtgVoltage::new().with_voltage(5000 / 20).into_bits()),
+            write::<OtgCurrent>(OtgCurrent::new().with_current(1500 / 25).into_bits()),
+            read::<ChargeOption3>(option3.into_bits()),
+            write::<ChargeOption3>(armed.into_bits()),
+            read::<ProchotStatus>(
+                status
+                    .with_stat_adapter_removal(StatAdapterRemoval::Triggered)
+                    .into_bits(),
+            ),
+            read::<VminActiveProtection>(vap.into_bits()),
+            write::<VminActiveProtection>(enabled.into_bits()),
+            read::<VminActiveProtection>(enabled.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<VminActiveProtection>(enabled.into_bits()),
+            read::<ProchotStatus>(removed.into_bits()),
+            read::<ChargeOption3>(armed.into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            read::<AdcVbus>(AdcVbus::new().with_voltage(5000 / 2).into_bits()),
+            read::<VminActiveProtection>(enabled.into_bits()),
+            read::<ProchotStatus>(removed.into_bits()),
+            read::<ChargeOption3>(armed.into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            read::<AdcVbus>(AdcVbus::new().into_bits()),
+            read::<VminActiveProtection>(enabled.into_bits()),
+            write::<VminActiveProtection>(vap.into_bits()),
+            read::<ChargeOption3>(armed.into_bits()),
+            write::<ChargeOption3>(option3.into_bits()),
+            read::<VminActiveProtection>(vap.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = FrsConfig {
+            voltage_mv: 5000,
+            current_ma: 50,
+        };
+
+        assert_eq!(bq.arm_frs(config).await, Err(Error::OutOfRange));
+
+        let config = FrsConfig {
+            current_ma: 1500,
+            ..config
+        };
+
+        bq.arm_frs(config).await.unwrap();
+        assert_eq!(bq.frs_status(&mut NoopDelay).await, Ok(FrsStatus::Armed));
+        assert_eq!(bq.frs_status(&mut NoopDelay).await, Ok(FrsStatus::Swapped));
+        assert_eq!(bq.frs_status(&mut NoopDelay).await, Ok(FrsStatus::Removed));
+        bq.disarm_frs().await.unwrap();
+        assert_eq!(bq.frs_status(&mut NoopDelay).await, Ok(FrsStatus::Disarmed));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_frs_keeps_other_status() {
+        let vap = VminActiveProtection::from_bits(VminActiveProtection::RESET).with_en_frs(FastRoleSwap::Enable);
+        let status = ProchotStatus::from_bits(ProchotStatus::RESET);
+        let tripped = status.with_stat_comp_(StatComp::Triggered);
+
+        let e = vec![
+            read::<VminActiveProtection>(vap.into_bits()),
+            read::<ProchotStatus>(tripped.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        // the FRS read clears the comparator status in the charger, but
+        // the trip is still reported
+        assert_eq!(bq.frs_status(&mut NoopDelay).await, Ok(FrsStatus::Armed));
+        bq.wait_comparator_trip(&mut NoopDelay, 10, 1).await.unwrap();
+        assert_eq!(
+            bq.wait_comparator_trip(&mut NoopDelay, 10, 1).await,
+            Err(Error::Timeout)
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,233 @@
+//! Input current optimizer (ICO) workflow, used to discover the
+//! current limit of adapters that don't advertise one.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{EnIcoMode, IinHost};
+use crate::{Bq2577x, Error};
+
+/// Input current optimizer run parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IcoConfig {
+    /// Time between IIN_DPM polls, in milliseconds.
+    pub poll_interval_ms: u32,
+
+    /// Number of consecutive identical IIN_DPM readings after which
+    /// the optimizer is considered converged.
+    pub settle_samples: u8,
+
+    /// Number of polls for which IIN_DPM still holding its pre-ICO
+    /// value doesn't count towards settling, giving the optimizer time
+    /// to start moving. If it hasn't moved by then, the adapter supports
+    /// the pre-ICO limit.
+    pub min_polls: u16,
+
+    /// Number of polls after which the run fails with
+    /// [`Error::Timeout`].
+    pub max_polls: u16,
+
+    /// Write the discovered limit to IIN_HOST. Otherwise the limit is
+    /// only reported: the previous ICO mode is restored, so the charger
+    /// goes back to enforcing the previous IIN_HOST.
+    pub apply: bool,
+}
+
+impl Default for IcoConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: 50,
+            settle_samples: 5,
+            min_polls: 20,
+            max_polls: 100,
+            apply: false,
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Runs the input current optimizer until IIN_DPM settles and
+    /// returns the discovered adapter current limit in milliamps.
+    ///
+    /// The previous ICO mode is restored afterwards, also on timeout.
+    /// With [`IcoConfig::apply`] set, the discovered limit is written
+    /// to IIN_HOST so it keeps being enforced; otherwise it is only
+    /// reported and the previous IIN_HOST stays in effect.
+    pub async fn run_ico<D: DelayNs>(&mut self, delay: &mut D, config: IcoConfig) -> Result<u16, Error<IFACE::Error>> {
+        let rac = self.charge_option1().await?.rsns_rac();
+        let option3 = self.charge_option3().await?;
+        let initial = self.iin_dpm().await?.current();
+
+        self.set_charge_option3(option3.with_en_ico_mode(EnIcoMode::Enable))
+            .await?;
+
+        let result = self.ico_settle(delay, &config, initial).await;
+
+        self.set_charge_option3(option3).await?;
+
+        let current = result?;
+
+        if config.apply {
+            self.set_iin_host(IinHost::new().with_current(current)).await?;
+        }
+
+        Ok(IinHost::new().with_current(current).milliamps(rac))
+    }
+
+    /// Polls IIN_DPM until it holds the same value for
+    /// `settle_samples` consecutive reads, returning the raw value. The
+    /// `initial` pre-ICO value only counts after `min_polls` polls.
+    async fn ico_settle<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        config: &IcoConfig,
+        initial: u16,
+    ) -> Result<u16, Error<IFACE::Error>> {
+        let mut last = None;
+        let mut stable = 0;
+
+        for poll in 0..config.max_polls {
+            delay.delay_ms(config.poll_interval_ms).await;
+
+            let current = self.iin_dpm().await?.current();
+
+            if current == initial && poll < config.min_polls {
+                continue;
+            }
+
+            if last == Some(current) {
+                stable += 1;
+            } else {
+                last = Some(current);
+                stable = 1;
+            }
+
+            if stable >= config.settle_samples {
+                return Ok(current);
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargeOption1, ChargeOption3, IinDpm, Register};
+    use crate::tests::{read, write};
+
+    fn iin_dpm(current: u16) -> u16 {
+        IinDpm::from_bits(0).with_current(current).into_bits()
+    }
+
+    #[tokio::test]
+    async fn test_ico_converges() {
+        let option3 = ChargeOption3::RESET;
+        let enabled = ChargeOption3::from_bits(option3)
+            .with_en_ico_mode(EnIcoMode::Enable)
+            .into_bits();
+
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ChargeOption3>(option3),
+            read::<IinDpm>(iin_dpm(0xc8)),
+            write::<ChargeOption3>(enabled),
+            // the host limit, before the optimizer moves
+            read::<IinDpm>(iin_dpm(0xc8)),
+            read::<IinDpm>(iin_dpm(0xc8)),
+            read::<IinDpm>(iin_dpm(0x60)),
+            read::<IinDpm>(iin_dpm(0x50)),
+            read::<IinDpm>(iin_dpm(0x50)),
+            write::<ChargeOption3>(option3),
+            write::<IinHost>(iin_dpm(0x50)),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = IcoConfig {
+            settle_samples: 2,
+            apply: true,
+            ..Default::default()
+        };
+
+        // 0x50 * 25mA with the POR 10mΩ RAC
+        assert_eq!(bq.run_ico(&mut NoopDelay, config).await, Ok(2000));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_ico_timeout() {
+        let option3 = ChargeOption3::RESET;
+        let enabled = ChargeOption3::from_bits(option3)
+            .with_en_ico_mode(EnIcoMode::Enable)
+            .into_bits();
+
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ChargeOption3>(option3),
+            read::<IinDpm>(iin_dpm(0xc8)),
+            write::<ChargeOption3>(enabled),
+            read::<IinDpm>(iin_dpm(0xc8)),
+            read::<IinDpm>(iin_dpm(0x60)),
+            read::<IinDpm>(iin_dpm(0x50)),
+            write::<ChargeOption3>(option3),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = IcoConfig {
+            max_polls: 3,
+            apply: true,
+            ..Default::default()
+        };
+
+        assert_eq!(bq.run_ico(&mut NoopDelay, config).await, Err(Error::Timeout));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_ico_keeps_host_limit() {
+        let option3 = ChargeOption3::RESET;
+        let enabled = ChargeOption3::from_bits(option3)
+            .with_en_ico_mode(EnIcoMode::Enable)
+            .into_bits();
+
+        // the optimizer never moves: the adapter takes the host limit
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<ChargeOption3>(option3),
+            read::<IinDpm>(iin_dpm(0x50)),
+            write::<ChargeOption3>(enabled),
+            read::<IinDpm>(iin_dpm(0x50)),
+            read::<IinDpm>(iin_dpm(0x50)),
+            read::<IinDpm>(iin_dpm(0x50)),
+            read::<IinDpm>(iin_dpm(0x50)),
+            write::<ChargeOption3>(option3),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = IcoConfig {
+            settle_samples: 2,
+            min_polls: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(bq.run_ico(&mut NoopDelay, config).await, Ok(2000));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
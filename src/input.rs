@@ -0,0 +1,364 @@
+//! Translation of a negotiated USB Power Delivery contract, or any other
+//! input source, into the charger's input limits.
+
+use crate::interface::RegisterInterface;
+use crate::registers::{AcocVth, AcovProtection, EnAcoc, IinHost, RsnsRac, VinDpm};
+use crate::{Bq2577x, Error};
+
+/// Kind of input source, as negotiated with the PD source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SourceKind {
+    /// Fixed supply PDO, SPR (up to 20V) or EPR (28V, 36V). The
+    /// charger can't take a 48V EPR contract.
+    Fixed,
+
+    /// SPR Programmable Power Supply APDO. The source current-limits
+    /// by folding back its voltage.
+    Pps,
+
+    /// EPR Adjustable Voltage Supply APDO.
+    Avs,
+}
+
+/// Negotiated input source contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InputSource {
+    kind: SourceKind,
+    voltage_mv: u16,
+    current_ma: u16,
+    derating: u8,
+}
+
+/// Input limits derived from an [`InputSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InputLimits {
+    /// Input current limit, in milliamps.
+    pub iin_host_ma: u16,
+
+    /// Input voltage DPM threshold, in millivolts.
+    pub vindpm_mv: u16,
+
+    /// Input overvoltage protection threshold.
+    pub acov: AcovProtection,
+
+    /// Input overcurrent protection threshold.
+    pub acoc: AcocVth,
+}
+
+impl InputSource {
+    /// Default input current derating, in percent.
+    pub const DERATING: u8 = 5;
+
+    /// Highest contract voltage the charger can take, in millivolts.
+    pub const MAX_VOLTAGE_MV: u16 = 36000;
+
+    /// Contracts at or above this current use the tighter ACOC limit,
+    /// in milliamps.
+    const HIGH_CURRENT_MA: u16 = 5000;
+
+    pub const fn new(kind: SourceKind, voltage_mv: u16, current_ma: u16) -> Self {
+        Self {
+            kind,
+            voltage_mv,
+            current_ma,
+            derating: Self::DERATING,
+        }
+    }
+
+    /// Fixed supply contract.
+    pub const fn fixed(voltage_mv: u16, current_ma: u16) -> Self {
+        Self::new(SourceKind::Fixed, voltage_mv, current_ma)
+    }
+
+    /// PPS contract at the requested operating point.
+    pub const fn pps(voltage_mv: u16, current_ma: u16) -> Self {
+        Self::new(SourceKind::Pps, voltage_mv, current_ma)
+    }
+
+    /// AVS contract at the requested operating point.
+    pub const fn avs(voltage_mv: u16, current_ma: u16) -> Self {
+        Self::new(SourceKind::Avs, voltage_mv, current_ma)
+    }
+
+    /// Overrides the input current derating, in percent of the
+    /// contract current. Values above 50% are limited to 50%.
+    pub const fn with_derating(self, percent: u8) -> Self {
+        let derating = if percent > 50 { 50 } else { percent };
+
+        Self { derating, ..self }
+    }
+
+    pub const fn kind(&self) -> SourceKind {
+        self.kind
+    }
+
+    pub const fn voltage_mv(&self) -> u16 {
+        self.voltage_mv
+    }
+
+    pub const fn current_ma(&self) -> u16 {
+        self.current_ma
+    }
+
+    /// Computes the input limits for this contract, or `None` if the
+    /// contract voltage exceeds what the charger can take.
+    pub const fn limits(&self) -> Option<InputLimits> {
+        let acov = match self.voltage_mv {
+            0..=15000 => AcovProtection::Threshold20V,
+            15001..=20000 => AcovProtection::Threshold25V,
+            20001..=28000 => AcovProtection::Threshold33V,
+            28001..=Self::MAX_VOLTAGE_MV => AcovProtection::Threshold41V,
+            _ => return None,
+        };
+
+        // A PPS source folds back its voltage when current limiting,
+        // so leave more headroom before VINDPM kicks in.
+        let vindpm_percent = match self.kind {
+            SourceKind::Fixed | SourceKind::Avs => 90,
+            SourceKind::Pps => 80,
+        };
+
+        let vindpm_mv = (self.voltage_mv as u32 * vindpm_percent / 100) as u16;
+        let iin_host_ma = (self.current_ma as u32 * (100 - self.derating as u32) / 100) as u16;
+
+        let acoc = if self.current_ma >= Self::HIGH_CURRENT_MA {
+            AcocVth::Threshold1_33
+        } else {
+            AcocVth::Threshold2
+        };
+
+        // VINDPM can't follow a 36V contract all the way
+        let vindpm_mv = if vindpm_mv < VinDpm::MIN_MV {
+            VinDpm::MIN_MV
+        } else if vindpm_mv > VinDpm::MAX_MV {
+            VinDpm::MAX_MV
+        } else {
+            vindpm_mv
+        };
+
+        Some(InputLimits {
+            iin_host_ma,
+            vindpm_mv,
+            acov,
+            acoc,
+        })
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Loosens the input limits for a source about to transition to
+    /// `next`: ACOV and ACOC are raised to cover both contracts and,
+    /// when moving to a lower voltage, VINDPM is lowered to `next`'s
+    /// threshold so the converter doesn't throttle the input on the way
+    /// down. IIN_HOST is left alone.
+    ///
+    /// Call this before requesting the new contract, then
+    /// [`Self::apply_input_source`] once the source has settled.
+    pub async fn prepare_input_source(&mut self, next: InputSource) -> Result<InputLimits, Error<IFACE::Error>> {
+        let limits = next.limits().ok_or(Error::UnsupportedInput)?;
+
+        let auto_charge = self.auto_charge().await?;
+        if limits.acov as u8 > auto_charge.acov_adj() as u8 {
+            self.set_auto_charge(auto_charge.with_acov_adj(limits.acov)).await?;
+        }
+
+        let option2 = self.charge_option2().await?;
+        let acoc = if option2.en_acoc() == EnAcoc::Enable && option2.acoc_vth() == AcocVth::Threshold2 {
+            AcocVth::Threshold2
+        } else {
+            limits.acoc
+        };
+        self.set_charge_option2(option2.with_acoc_vth(acoc).with_en_acoc(EnAcoc::Enable))
+            .await?;
+
+        if limits.vindpm_mv < self.vin_dpm().await?.millivolts() {
+            self.set_vin_dpm(VinDpm::new().with_millivolts(limits.vindpm_mv))
+                .await?;
+        }
+
+        Ok(limits)
+    }
+
+    /// Programs the input current limit, VINDPM threshold and input
+    /// over-voltage/over-current protection for a negotiated contract.
+    ///
+    /// Call this once the source has settled at the contract voltage:
+    /// a VINDPM threshold above VBUS stops the converter drawing input
+    /// current, and an ACOV threshold below it trips. When changing
+    /// contracts, call [`Self::prepare_input_source`] before the source
+    /// transitions.
+    pub async fn apply_input_source(&mut self, source: InputSource) -> Result<InputLimits, Error<IFACE::Error>> {
+        let limits = source.limits().ok_or(Error::UnsupportedInput)?;
+        let rac: RsnsRac = self.charge_option1().await?.rsns_rac();
+
+        let auto_charge = self.auto_charge().await?;
+        self.set_auto_charge(auto_charge.with_acov_adj(limits.acov)).await?;
+
+        let option2 = self.charge_option2().await?;
+        self.set_charge_option2(option2.with_acoc_vth(limits.acoc).with_en_acoc(EnAcoc::Enable))
+            .await?;
+
+        self.set_vin_dpm(VinDpm::new().with_millivolts(limits.vindpm_mv))
+            .await?;
+        self.set_iin_host(IinHost::new().with_milliamps(limits.iin_host_ma, rac))
+            .await?;
+
+        Ok(limits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{AutoCharge, ChargeOption1, ChargeOption2, Register};
+    use crate::tests::{read, write};
+
+    macro_rules! test_contract {
+        ($source:expr, $iin:expr, $vindpm:expr, $acov:ident, $acoc:ident) => {
+            assert_eq!(
+                $source.limits(),
+                Some(InputLimits {
+                    iin_host_ma: $iin,
+                    vindpm_mv: $vindpm,
+                    acov: AcovProtection::$acov,
+                    acoc: AcocVth::$acoc,
+                })
+            );
+        };
+    }
+
+    #[test]
+    fn test_vin_dpm_units() {
+        // voltage sits in bits 12:2
+        assert_eq!(VinDpm::from_bits(VinDpm::RESET).millivolts(), 3200);
+        assert_eq!(VinDpm::from_bits(0x0e10).millivolts(), 18000);
+        assert_eq!(VinDpm::new().with_millivolts(27000).into_bits(), 0x1518);
+        assert_eq!(VinDpm::new().with_millivolts(30000).millivolts(), 27000);
+    }
+
+    #[test]
+    fn test_contract_limits() {
+        test_contract!(InputSource::fixed(5000, 3000), 2850, 4500, Threshold20V, Threshold2);
+        test_contract!(InputSource::fixed(9000, 3000), 2850, 8100, Threshold20V, Threshold2);
+        test_contract!(InputSource::fixed(15000, 3000), 2850, 13500, Threshold20V, Threshold2);
+        test_contract!(
+            InputSource::fixed(20000, 5000),
+            4750,
+            18000,
+            Threshold25V,
+            Threshold1_33
+        );
+        test_contract!(
+            InputSource::fixed(28000, 5000),
+            4750,
+            25200,
+            Threshold33V,
+            Threshold1_33
+        );
+        test_contract!(
+            InputSource::fixed(36000, 5000),
+            4750,
+            27000,
+            Threshold41V,
+            Threshold1_33
+        );
+        assert_eq!(InputSource::fixed(48000, 5000).limits(), None);
+
+        test_contract!(InputSource::pps(5000, 3000), 2850, 4000, Threshold20V, Threshold2);
+        test_contract!(InputSource::pps(4000, 3000), 2850, 3200, Threshold20V, Threshold2);
+        test_contract!(InputSource::avs(28000, 5000), 4750, 25200, Threshold33V, Threshold1_33);
+        test_contract!(
+            InputSource::fixed(20000, 3000).with_derating(10),
+            2700,
+            18000,
+            Threshold25V,
+            Threshold2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_input_source() {
+        // 20V/5A with a 5mΩ RAC: IIN_HOST = 4750mA / 50mA = 95 (5Fh),
+        // VINDPM = 18000mV / 20mV = 900 (384h)
+        let auto_charge = AutoCharge::new()
+            .with_acov_adj(AcovProtection::Threshold25V)
+            .into_bits();
+        let option2 = ChargeOption2::new()
+            .with_acoc_vth(AcocVth::Threshold1_33)
+            .with_en_acoc(EnAcoc::Enable)
+            .into_bits();
+        let option1 = ChargeOption1::new().with_rsns_rac(RsnsRac::MilliOhms5).into_bits();
+
+        let e = vec![
+            read::<ChargeOption1>(option1),
+            read::<AutoCharge>(AutoCharge::RESET),
+            write::<AutoCharge>(auto_charge),
+            read::<ChargeOption2>(ChargeOption2::RESET),
+            write::<ChargeOption2>(option2),
+            write::<VinDpm>(0x0e10),
+            write::<IinHost>(0x017c),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let limits = bq.apply_input_source(InputSource::fixed(20000, 5000)).await.unwrap();
+        assert_eq!(limits.iin_host_ma, 4750);
+
+        assert_eq!(
+            bq.apply_input_source(InputSource::fixed(48000, 5000)).await,
+            Err(Error::UnsupportedInput)
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_prepare_input_source() {
+        let auto_5v = AutoCharge::from_bits(AutoCharge::RESET).with_acov_adj(AcovProtection::Threshold20V);
+        let auto_20v = auto_5v.with_acov_adj(AcovProtection::Threshold25V);
+        let option2_5v = ChargeOption2::from_bits(ChargeOption2::RESET)
+            .with_acoc_vth(AcocVth::Threshold2)
+            .with_en_acoc(EnAcoc::Enable);
+        let option2_20v = option2_5v.with_acoc_vth(AcocVth::Threshold1_33);
+        let vindpm_5v = VinDpm::new().with_millivolts(4500);
+        let vindpm_20v = VinDpm::new().with_millivolts(18000);
+
+        let e = vec![
+            // 5V/3A to 20V/5A: ACOV raised, the looser ACOC kept and
+            // VINDPM left at 4.5V until the source has moved
+            read::<AutoCharge>(auto_5v.into_bits()),
+            write::<AutoCharge>(auto_20v.into_bits()),
+            read::<ChargeOption2>(option2_5v.into_bits()),
+            write::<ChargeOption2>(option2_5v.into_bits()),
+            read::<VinDpm>(vindpm_5v.into_bits()),
+            // 20V/5A to 5V/3A: ACOV kept until the source has moved and
+            // VINDPM lowered first
+            read::<AutoCharge>(auto_20v.into_bits()),
+            read::<ChargeOption2>(option2_20v.into_bits()),
+            write::<ChargeOption2>(option2_5v.into_bits()),
+            read::<VinDpm>(vindpm_20v.into_bits()),
+            write::<VinDpm>(vindpm_5v.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        bq.prepare_input_source(InputSource::fixed(20000, 5000)).await.unwrap();
+        bq.prepare_input_source(InputSource::fixed(5000, 3000)).await.unwrap();
+
+        assert_eq!(
+            bq.prepare_input_source(InputSource::fixed(48000, 5000)).await,
+            Err(Error::UnsupportedInput)
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
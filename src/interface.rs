@@ -0,0 +1,182 @@
+//! Register transports: how the driver reaches the charger's registers.
+//!
+//! [`I2cInterface`] talks to the charger over I2C or SMBus. Anything else,
+//! such as an EC host mailbox or a USB-to-I2C bridge, implements
+//! [`RegisterInterface`] and is passed to [`Bq2577x::with_interface`].
+//!
+//! [`Bq2577x::with_interface`]: crate::Bq2577x::with_interface
+
+use core::convert::Infallible;
+
+use embedded_hal_async::i2c::I2c;
+
+/// Word and block access to the charger's 16-bit registers.
+#[allow(async_fn_in_trait)]
+pub trait RegisterInterface {
+    /// Transport error.
+    type Error: core::fmt::Debug;
+
+    /// Reads the register at `reg`.
+    async fn read_word(&mut self, reg: u8) -> Result<u16, Self::Error>;
+
+    /// Writes `value` to the register at `reg`.
+    async fn write_word(&mut self, reg: u8, value: u16) -> Result<(), Self::Error>;
+
+    /// Reads `buf.len()` bytes starting at `reg`, each register most
+    /// significant byte first.
+    async fn read_block(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// Charger on an I2C or SMBus bus.
+pub struct I2cInterface<I2C: I2c> {
+    /// The concrete I2C bus instance
+    i2c: I2C,
+
+    /// This device's I2C address
+    addr: u8,
+}
+
+impl<I2C: I2c> I2cInterface<I2C> {
+    /// The charger's fixed I2C address.
+    pub const ADDR: u8 = 0x09;
+
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c, addr: Self::ADDR }
+    }
+
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: I2c> RegisterInterface for I2cInterface<I2C> {
+    type Error = I2C::Error;
+
+    async fn read_word(&mut self, reg: u8) -> Result<u16, Self::Error> {
+        let mut bytes = [0; 2];
+        self.read_block(reg, &mut bytes).await?;
+
+        Ok(u16::from_be_bytes(bytes))
+    }
+
+    async fn write_word(&mut self, reg: u8, value: u16) -> Result<(), Self::Error> {
+        let mut bytes = [0u8; 3];
+        let content = value.to_be_bytes();
+
+        bytes[0] = reg;
+        bytes[1] = content[0];
+        bytes[2] = content[1];
+
+        self.i2c.write(self.addr, &bytes).await
+    }
+
+    async fn read_block(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.i2c.write_read(self.addr, &[reg], buf).await
+    }
+}
+
+/// In-memory register file, for simulation and tests. Reads return what
+/// was last written; nothing reacts to the writes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegisterFile {
+    regs: [u16; 256],
+}
+
+impl RegisterFile {
+    /// Register file with every register zero.
+    pub const fn new() -> Self {
+        Self { regs: [0; 256] }
+    }
+
+    /// Register value at `reg`.
+    pub const fn get(&self, reg: u8) -> u16 {
+        self.regs[reg as usize]
+    }
+
+    /// Sets the register value at `reg`, as the charger would.
+    pub fn set(&mut self, reg: u8, value: u16) {
+        self.regs[reg as usize] = value;
+    }
+}
+
+impl Default for RegisterFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegisterInterface for RegisterFile {
+    type Error = Infallible;
+
+    async fn read_word(&mut self, reg: u8) -> Result<u16, Self::Error> {
+        Ok(self.get(reg))
+    }
+
+    async fn write_word(&mut self, reg: u8, value: u16) -> Result<(), Self::Error> {
+        self.set(reg, value);
+
+        Ok(())
+    }
+
+    /// Reads consecutive registers from `reg`, wrapping after 0xFF. An
+    /// odd length ends with the high byte of the last register.
+    async fn read_block(&mut self, reg: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        for (i, chunk) in buf.chunks_mut(2).enumerate() {
+            let word = self.get(reg.wrapping_add(i as u8)).to_be_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use super::*;
+    use crate::registers::{ChargeCurrent, ChargeOption0, ChargeVoltage, Register};
+    use crate::Bq2577x;
+
+    #[tokio::test]
+    async fn test_i2c_block() {
+        let e = vec![Transaction::write_read(0x09, vec![0x14], vec![0x01, 0x02, 0x03, 0x04])];
+
+        let mut iface = I2cInterface::new(Mock::new(&e));
+
+        let mut buf = [0; 4];
+        iface.read_block(0x14, &mut buf).await.unwrap();
+        assert_eq!(buf, [0x01, 0x02, 0x03, 0x04]);
+
+        let mut mock = iface.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_register_file() {
+        let mut regs = RegisterFile::new();
+        regs.set(ChargeOption0::ADDR, ChargeOption0::RESET);
+
+        let mut bq = Bq2577x::with_interface(regs);
+
+        bq.set_charge_voltage(ChargeVoltage::new().with_millivolts(12600))
+            .await
+            .unwrap();
+        bq.set_charge_current(ChargeCurrent::new().with_current(0x0102))
+            .await
+            .unwrap();
+
+        assert_eq!(bq.charge_voltage().await.unwrap().millivolts(), 12600);
+        assert_eq!(bq.charge_option0().await.unwrap().into_bits(), ChargeOption0::RESET);
+
+        let mut regs = bq.into_interface();
+
+        let current = ChargeCurrent::new().with_current(0x0102).into_bits().to_be_bytes();
+        let voltage = ChargeVoltage::new().with_millivolts(12600).into_bits().to_be_bytes();
+
+        // ChargeCurrent and ChargeVoltage are consecutive registers
+        let mut buf = [0; 3];
+        regs.read_block(ChargeCurrent::ADDR, &mut buf).await.unwrap();
+        assert_eq!(buf, [current[0], current[1], voltage[0]]);
+    }
+}
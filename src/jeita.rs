@@ -0,0 +1,312 @@
+//! JEITA temperature-compensated charging: charge current and voltage
+//! follow the battery temperature zone.
+
+use crate::cells::CellCount;
+use crate::interface::RegisterInterface;
+use crate::registers::{ChargeCurrent, ChargeVoltage};
+use crate::thermistor::Thermistor;
+use crate::{Bq2577x, Error};
+
+/// Battery temperature source for [`Bq2577x::poll_jeita`].
+pub trait TemperatureSource {
+    /// Battery temperature in degrees Celsius, or `None` if it can't be
+    /// measured.
+    fn celsius(&mut self) -> Option<f32>;
+}
+
+impl<F: FnMut() -> Option<f32>> TemperatureSource for F {
+    fn celsius(&mut self) -> Option<f32> {
+        self()
+    }
+}
+
+/// JEITA temperature zone, from coldest to hottest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum JeitaZone {
+    /// Below T1: charging suspended.
+    Cold,
+
+    /// T1 to T2: reduced charge current.
+    Cool,
+
+    /// T2 to T3: full charge current and voltage.
+    Normal,
+
+    /// T3 to T4: reduced charge voltage.
+    Warm,
+
+    /// Above T4, or temperature unknown: charging suspended.
+    Hot,
+}
+
+/// JEITA zone table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct JeitaConfig {
+    /// Zone boundaries T1 to T4, in degrees Celsius.
+    pub thresholds: [f32; 4],
+
+    /// Margin by which the temperature must cross back over a boundary
+    /// before moving to a less restrictive zone, in degrees Celsius.
+    pub hysteresis: f32,
+
+    /// Charge current in the normal and warm zones, in milliamps.
+    pub current_ma: u16,
+
+    /// Charge current in the cool zone, in milliamps.
+    pub cool_current_ma: u16,
+
+    /// Charge voltage in the cool and normal zones, in millivolts.
+    pub voltage_mv: u16,
+
+    /// Charge voltage in the warm zone, in millivolts.
+    pub warm_voltage_mv: u16,
+}
+
+impl JeitaConfig {
+    /// Standard 0°C, 10°C, 45°C and 60°C boundaries with 2°C of
+    /// hysteresis, half the charge current when cool and 100mV per
+    /// cell less when warm, for a pack of `cells`. Both charge voltages
+    /// are clamped to [`CellCount::charge_voltage_mv`].
+    pub const fn new(cells: CellCount, current_ma: u16, voltage_mv: u16) -> Self {
+        let voltage_mv = cells.clamp_charge_voltage_mv(voltage_mv);
+        let warm_voltage_mv = voltage_mv.saturating_sub(cells.cells() as u16 * 100);
+
+        Self {
+            thresholds: [0.0, 10.0, 45.0, 60.0],
+            hysteresis: 2.0,
+            current_ma,
+            cool_current_ma: current_ma / 2,
+            voltage_mv,
+            warm_voltage_mv: cells.clamp_charge_voltage_mv(warm_voltage_mv),
+        }
+    }
+
+    /// Zone for `celsius` without hysteresis.
+    fn zone(&self, celsius: f32) -> JeitaZone {
+        let [t1, t2, t3, t4] = self.thresholds;
+
+        if celsius < t1 {
+            JeitaZone::Cold
+        } else if celsius < t2 {
+            JeitaZone::Cool
+        } else if celsius <= t3 {
+            JeitaZone::Normal
+        } else if celsius <= t4 {
+            JeitaZone::Warm
+        } else {
+            JeitaZone::Hot
+        }
+    }
+
+    /// Charge current and voltage for `zone`, in milliamps and
+    /// millivolts.
+    pub const fn setpoint(&self, zone: JeitaZone) -> (u16, u16) {
+        match zone {
+            JeitaZone::Cold | JeitaZone::Hot => (0, self.voltage_mv),
+            JeitaZone::Cool => (self.cool_current_ma, self.voltage_mv),
+            JeitaZone::Normal => (self.current_ma, self.voltage_mv),
+            JeitaZone::Warm => (self.current_ma, self.warm_voltage_mv),
+        }
+    }
+}
+
+/// JEITA controller state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Jeita {
+    pub config: JeitaConfig,
+    zone: Option<JeitaZone>,
+}
+
+impl Jeita {
+    pub const fn new(config: JeitaConfig) -> Self {
+        Self { config, zone: None }
+    }
+
+    /// Active zone, or `None` before the first update.
+    pub const fn zone(&self) -> Option<JeitaZone> {
+        self.zone
+    }
+
+    /// Moves to the zone for `celsius` and returns it. Moving to a more
+    /// restrictive zone is immediate; moving back towards
+    /// [`JeitaZone::Normal`] needs the temperature to clear the
+    /// boundary by the hysteresis margin. An unknown temperature is
+    /// treated as [`JeitaZone::Hot`].
+    pub fn update(&mut self, celsius: Option<f32>) -> JeitaZone {
+        let Some(celsius) = celsius else {
+            self.zone = Some(JeitaZone::Hot);
+            return JeitaZone::Hot;
+        };
+
+        let raw = self.config.zone(celsius);
+        let hysteresis = self.config.hysteresis;
+
+        let zone = match self.zone {
+            Some(current) if current < JeitaZone::Normal && raw > current && raw <= JeitaZone::Normal => {
+                self.config.zone(celsius - hysteresis).max(current)
+            }
+            Some(current) if current > JeitaZone::Normal && raw < current && raw >= JeitaZone::Normal => {
+                self.config.zone(celsius + hysteresis).min(current)
+            }
+            _ => raw,
+        };
+
+        self.zone = Some(zone);
+
+        zone
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Updates `jeita` with a temperature from `source` and returns the
+    /// resulting zone. Call periodically, well within the charger's
+    /// watchdog timeout: the charge current and voltage are programmed
+    /// on every poll, which restores CHARGE_CURRENT after the charger
+    /// zeroed it, e.g. on watchdog expiry or adapter removal, and
+    /// refreshes the watchdog.
+    pub async fn poll_jeita<T: TemperatureSource>(
+        &mut self,
+        jeita: &mut Jeita,
+        source: &mut T,
+    ) -> Result<JeitaZone, Error<IFACE::Error>> {
+        let zone = jeita.update(source.celsius());
+        let (current_ma, voltage_mv) = jeita.config.setpoint(zone);
+        let rsr = self.charge_option1().await?.rsns_rsr();
+
+        self.set_charge_voltage(ChargeVoltage::new().with_millivolts(voltage_mv))
+            .await?;
+        self.set_charge_current(ChargeCurrent::new().with_milliamps(current_ma, rsr))
+            .await?;
+
+        Ok(zone)
+    }
+
+    /// Like [`Self::poll_jeita`], with the temperature read from a
+    /// thermistor on CMPIN_TR.
+    pub async fn poll_jeita_thermistor(
+        &mut self,
+        jeita: &mut Jeita,
+        thermistor: &Thermistor,
+    ) -> Result<JeitaZone, Error<IFACE::Error>> {
+        let celsius = self.thermistor_celsius(thermistor).await?;
+
+        self.poll_jeita(jeita, &mut || celsius).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::interface::RegisterFile;
+    use crate::registers::{AdcCmpinTr, ChargeOption1, Register, RsnsRsr};
+    use crate::tests::{read, write};
+    use crate::thermistor::ThermistorModel;
+
+    #[test]
+    fn test_jeita_zones() {
+        let mut jeita = Jeita::new(JeitaConfig::new(CellCount::Three, 3000, 12600));
+
+        assert_eq!(jeita.zone(), None);
+        assert_eq!(jeita.update(Some(25.0)), JeitaZone::Normal);
+        assert_eq!(jeita.update(Some(46.0)), JeitaZone::Warm);
+        // within hysteresis of T3
+        assert_eq!(jeita.update(Some(44.0)), JeitaZone::Warm);
+        assert_eq!(jeita.update(Some(42.0)), JeitaZone::Normal);
+        assert_eq!(jeita.update(Some(61.0)), JeitaZone::Hot);
+        assert_eq!(jeita.update(Some(50.0)), JeitaZone::Warm);
+        assert_eq!(jeita.update(Some(5.0)), JeitaZone::Cool);
+        assert_eq!(jeita.update(Some(-1.0)), JeitaZone::Cold);
+        assert_eq!(jeita.update(Some(1.0)), JeitaZone::Cold);
+        assert_eq!(jeita.update(Some(11.0)), JeitaZone::Cool);
+        assert_eq!(jeita.update(Some(12.0)), JeitaZone::Normal);
+        assert_eq!(jeita.update(None), JeitaZone::Hot);
+        assert_eq!(jeita.zone(), Some(JeitaZone::Hot));
+
+        let config = jeita.config;
+        assert_eq!(config.setpoint(JeitaZone::Cold), (0, 12600));
+        assert_eq!(config.setpoint(JeitaZone::Cool), (1500, 12600));
+        assert_eq!(config.setpoint(JeitaZone::Normal), (3000, 12600));
+        assert_eq!(config.setpoint(JeitaZone::Warm), (3000, 12300));
+
+        // 2s: 9000mV at most, and no lower than 7000mV when warm
+        let config = JeitaConfig::new(CellCount::Two, 3000, 12600);
+        assert_eq!(config.setpoint(JeitaZone::Normal), (3000, 9000));
+        assert_eq!(config.setpoint(JeitaZone::Warm), (3000, 8800));
+        let config = JeitaConfig::new(CellCount::Two, 3000, 7000);
+        assert_eq!(config.setpoint(JeitaZone::Warm), (3000, 7000));
+    }
+
+    #[tokio::test]
+    async fn test_poll_jeita() {
+        // POR RSR is 5mΩ: 8mA step
+        let e = vec![
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12300 / 4).into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(3000 / 8).into_bits()),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12300 / 4).into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(3000 / 8).into_bits()),
+            read::<AdcCmpinTr>(2500),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12600 / 4).into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(3000 / 8).into_bits()),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12600 / 4).into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(3000 / 8).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let mut jeita = Jeita::new(JeitaConfig::new(CellCount::Three, 3000, 12600));
+
+        assert_eq!(bq.poll_jeita(&mut jeita, &mut || Some(50.0)).await, Ok(JeitaZone::Warm));
+        assert_eq!(bq.poll_jeita(&mut jeita, &mut || Some(55.0)).await, Ok(JeitaZone::Warm));
+
+        let model = ThermistorModel::Beta {
+            r25_ohms: 10_000.0,
+            beta: 3435.0,
+        };
+        let thermistor = Thermistor::new(model, 5000.0, 10_000.0);
+
+        assert_eq!(
+            bq.poll_jeita_thermistor(&mut jeita, &thermistor).await,
+            Ok(JeitaZone::Normal)
+        );
+        assert_eq!(
+            bq.poll_jeita(&mut jeita, &mut || Some(25.0)).await,
+            Ok(JeitaZone::Normal)
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_poll_jeita_restores_current() {
+        let mut regs = RegisterFile::new();
+        regs.set(ChargeOption1::ADDR, ChargeOption1::RESET);
+
+        let mut bq = Bq2577x::with_interface(regs);
+        let mut jeita = Jeita::new(JeitaConfig::new(CellCount::Three, 3000, 12600));
+
+        assert_eq!(
+            bq.poll_jeita(&mut jeita, &mut || Some(25.0)).await,
+            Ok(JeitaZone::Normal)
+        );
+
+        // the charger zeroes CHARGE_CURRENT on watchdog expiry
+        bq.set_charge_current(ChargeCurrent::new()).await.unwrap();
+
+        assert_eq!(
+            bq.poll_jeita(&mut jeita, &mut || Some(25.0)).await,
+            Ok(JeitaZone::Normal)
+        );
+        assert_eq!(bq.charge_current().await.unwrap().milliamps(RsnsRsr::MilliOhms5), 3000);
+    }
+}
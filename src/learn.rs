@@ -0,0 +1,370 @@
+//! Learn mode, which discharges the battery with the adapter present so
+//! that a fuel gauge can calibrate over a controlled discharge.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{
+    AdcEn, AdcOption, AdcRate, ChargeInhibit, ChargeOption0, ChargeOption1, EnAdcVbat, Fault, Learn, ProchotStatus,
+    ShipDchg, StatAdapterRemoval, VsysMin,
+};
+use crate::{Bq2577x, Error};
+
+const ADAPTER_REMOVAL: ProchotStatus = ProchotStatus::new().with_stat_adapter_removal(StatAdapterRemoval::Triggered);
+
+/// Learn cycle parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LearnConfig {
+    /// Battery voltage at which the cycle ends, in millivolts.
+    ///
+    /// There is no discharged capacity target: the charger only measures
+    /// discharge current in battery only or OTG mode (see
+    /// [`crate::registers::AdcIbat`]), and learn mode runs with the
+    /// adapter present, so IBAT can't be integrated during the cycle.
+    /// The fuel gauge being calibrated measures the discharged capacity.
+    pub target_mv: u16,
+
+    /// Time between VBAT polls, in milliseconds.
+    pub poll_interval_ms: u32,
+
+    /// Number of polls after which the cycle fails with
+    /// [`Error::Timeout`].
+    pub max_polls: u32,
+
+    /// Minimum system voltage to use during the cycle, in millivolts,
+    /// so that the discharge can go below the configured VSYS_MIN.
+    /// `None` keeps the current setting. The cycle fails with
+    /// [`Error::OutOfRange`] if it is outside the register range.
+    pub vsys_min_mv: Option<u16>,
+}
+
+impl LearnConfig {
+    pub const fn new(target_mv: u16) -> Self {
+        Self {
+            target_mv,
+            poll_interval_ms: 1000,
+            max_polls: 6 * 60 * 60,
+            vsys_min_mv: None,
+        }
+    }
+}
+
+/// Reason a learn cycle ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LearnStop {
+    /// The battery voltage dropped to [`LearnConfig::target_mv`].
+    TargetReached,
+
+    /// The adapter was removed mid-cycle.
+    AdapterRemoved,
+
+    /// The charger reported a REGN, OCP or BATOVP fault.
+    Fault,
+}
+
+/// Result of a learn cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LearnReport {
+    /// Reason the cycle ended.
+    pub stop: LearnStop,
+
+    /// Last battery voltage reading, in millivolts.
+    pub vbat_mv: i32,
+}
+
+/// Registers touched by a learn cycle, saved so they can be restored.
+struct Saved {
+    option0: ChargeOption0,
+    option1: ChargeOption1,
+    vsys_min: VsysMin,
+    adc_option: AdcOption,
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Runs a learn cycle: inhibits charging, enters learn mode and
+    /// discharges the battery until the configured target is reached,
+    /// the adapter is removed or the charger reports a fault.
+    ///
+    /// Charge inhibit, learn mode, ship discharge, VSYS_MIN and the ADC
+    /// configuration are restored afterwards, also on timeout or bus
+    /// error.
+    ///
+    /// Adapter removal is detected through
+    /// [`crate::registers::ProchotStatus::stat_adapter_removal`], whose
+    /// status is latched until read, so it is cleared when the cycle
+    /// starts.
+    pub async fn run_learn_cycle<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        config: LearnConfig,
+    ) -> Result<LearnReport, Error<IFACE::Error>> {
+        if let Some(mv) = config.vsys_min_mv {
+            if !(VsysMin::MIN_MV..=VsysMin::MAX_MV).contains(&mv) {
+                return Err(Error::OutOfRange);
+            }
+        }
+
+        let saved = Saved {
+            option0: self.charge_option0().await?,
+            option1: self.charge_option1().await?,
+            vsys_min: self.vsys_min().await?,
+            adc_option: self.adc_option().await?,
+        };
+
+        let result = self.learn_cycle(delay, &config, &saved).await;
+        let restored = self.learn_restore(&saved).await;
+
+        let report = result?;
+        restored?;
+
+        Ok(report)
+    }
+
+    async fn learn_cycle<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        config: &LearnConfig,
+        saved: &Saved,
+    ) -> Result<LearnReport, Error<IFACE::Error>> {
+        // clear stale latched status
+        self.prochot_take(ADAPTER_REMOVAL).await?;
+        self.charger_status0().await?;
+
+        self.set_adc_option(
+            saved
+                .adc_option
+                .with_en_adc_vbat(EnAdcVbat::Enable)
+                .with_adc_rate(AdcRate::Continuous)
+                .with_adc_en(AdcEn::Enable),
+        )
+        .await?;
+
+        self.set_charge_option1(saved.option1.with_en_ship_dchg(ShipDchg::Disable))
+            .await?;
+
+        if let Some(mv) = config.vsys_min_mv {
+            self.set_vsys_min(VsysMin::new().with_millivolts(mv)).await?;
+        }
+
+        // ChargeInhibit::Disable inhibits charging
+        self.set_charge_option0(
+            saved
+                .option0
+                .with_chrg_inhibit(ChargeInhibit::Disable)
+                .with_en_learn(Learn::Enable),
+        )
+        .await?;
+
+        self.learn_monitor(delay, config).await
+    }
+
+    async fn learn_monitor<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        config: &LearnConfig,
+    ) -> Result<LearnReport, Error<IFACE::Error>> {
+        for _ in 0..config.max_polls {
+            delay.delay_ms(config.poll_interval_ms).await;
+
+            let prochot = self.prochot_take(ADAPTER_REMOVAL).await?;
+            let status = self.charger_status0().await?;
+            let vbat_mv = self.adc_vbat().await?.millivolts();
+
+            let stop = if prochot.stat_adapter_removal() == StatAdapterRemoval::Triggered {
+                Some(LearnStop::AdapterRemoved)
+            } else if status.fault_regn() == Fault::Fault
+                || status.fault_ocp() == Fault::Fault
+                || status.fault_batovp() == Fault::Fault
+            {
+                Some(LearnStop::Fault)
+            } else if vbat_mv <= config.target_mv as i32 {
+                Some(LearnStop::TargetReached)
+            } else {
+                None
+            };
+
+            if let Some(stop) = stop {
+                return Ok(LearnReport { stop, vbat_mv });
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Leaves learn mode first so the battery stops discharging, then
+    /// restores the rest. Every register is attempted even if an
+    /// earlier write fails; the first error is returned.
+    async fn learn_restore(&mut self, saved: &Saved) -> Result<(), Error<IFACE::Error>> {
+        let option0 = self.set_charge_option0(saved.option0).await;
+        let vsys_min = self.set_vsys_min(saved.vsys_min).await;
+        let option1 = self.set_charge_option1(saved.option1).await;
+        let adc_option = self.set_adc_option(saved.adc_option).await;
+
+        option0.and(vsys_min).and(option1).and(adc_option)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use super::*;
+    use crate::registers::{AdcIbat, AdcVbat, ChargerStatus0, ProchotStatus, Register, RsnsRsr};
+    use crate::tests::{read, write};
+
+    fn enter() -> Vec<Transaction> {
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET);
+        let adc_option = AdcOption::from_bits(AdcOption::RESET)
+            .with_en_adc_vbat(EnAdcVbat::Enable)
+            .with_adc_rate(AdcRate::Continuous)
+            .with_adc_en(AdcEn::Enable);
+        let option0 = ChargeOption0::from_bits(ChargeOption0::RESET)
+            .with_chrg_inhibit(ChargeInhibit::Disable)
+            .with_en_learn(Learn::Enable);
+
+        vec![
+            read::<ChargeOption0>(ChargeOption0::RESET),
+            read::<ChargeOption1>(ChargeOption1::RESET),
+            read::<VsysMin>(VsysMin::RESET),
+            read::<AdcOption>(AdcOption::RESET),
+            read::<ProchotStatus>(ProchotStatus::RESET),
+            read::<ChargerStatus0>(ChargerStatus0::RESET),
+            write::<AdcOption>(adc_option.into_bits()),
+            write::<ChargeOption1>(option1.with_en_ship_dchg(ShipDchg::Disable).into_bits()),
+            write::<ChargeOption0>(option0.into_bits()),
+        ]
+    }
+
+    fn restore() -> Vec<Transaction> {
+        vec![
+            write::<ChargeOption0>(ChargeOption0::RESET),
+            write::<VsysMin>(VsysMin::RESET),
+            write::<ChargeOption1>(ChargeOption1::RESET),
+            write::<AdcOption>(AdcOption::RESET),
+        ]
+    }
+
+    fn poll(prochot: u16, status: u16, vbat_mv: i16) -> Vec<Transaction> {
+        vec![
+            read::<ProchotStatus>(prochot),
+            read::<ChargerStatus0>(status),
+            read::<AdcVbat>(AdcVbat::new().with_voltage(vbat_mv).into_bits()),
+        ]
+    }
+
+    #[test]
+    fn test_adc_units() {
+        let ibat = AdcIbat::new().with_current(-1000);
+
+        assert_eq!(ibat.milliamps(RsnsRsr::MilliOhms5), -1000);
+        assert_eq!(ibat.milliamps(RsnsRsr::MilliOhms2), -2500);
+        assert_eq!(AdcVbat::new().with_voltage(12000).millivolts(), 12000);
+        assert_eq!(VsysMin::new().with_millivolts(6600).into_bits(), VsysMin::RESET);
+        assert_eq!(VsysMin::new().with_millivolts(30000).millivolts(), 21000);
+    }
+
+    #[tokio::test]
+    async fn test_learn_vsys_min_range() {
+        let mut bq = Bq2577x::new(Mock::new(&[]));
+
+        let config = LearnConfig {
+            vsys_min_mv: Some(30000),
+            ..LearnConfig::new(9000)
+        };
+
+        assert_eq!(bq.run_learn_cycle(&mut NoopDelay, config).await, Err(Error::OutOfRange));
+
+        let config = LearnConfig {
+            vsys_min_mv: Some(4000),
+            ..config
+        };
+
+        assert_eq!(bq.run_learn_cycle(&mut NoopDelay, config).await, Err(Error::OutOfRange));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_learn_to_voltage() {
+        let idle = ProchotStatus::RESET;
+        let ok = ChargerStatus0::RESET;
+
+        let mut e = enter();
+        e.extend(poll(idle, ok, 12600));
+        e.extend(poll(idle, ok, 12000));
+        e.extend(restore());
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = LearnConfig {
+            poll_interval_ms: 1000,
+            ..LearnConfig::new(12000)
+        };
+
+        let report = bq.run_learn_cycle(&mut NoopDelay, config).await.unwrap();
+        assert_eq!(report.stop, LearnStop::TargetReached);
+        assert_eq!(report.vbat_mv, 12000);
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_learn_adapter_removed() {
+        let removed = ProchotStatus::from_bits(ProchotStatus::RESET)
+            .with_stat_adapter_removal(StatAdapterRemoval::Triggered)
+            .into_bits();
+
+        let mut e = enter();
+        e.extend(poll(removed, ChargerStatus0::RESET, 12600));
+        e.extend(restore());
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let report = bq
+            .run_learn_cycle(&mut NoopDelay, LearnConfig::new(9000))
+            .await
+            .unwrap();
+        assert_eq!(report.stop, LearnStop::AdapterRemoved);
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_learn_fault_and_timeout() {
+        let fault = ChargerStatus0::from_bits(ChargerStatus0::RESET)
+            .with_fault_ocp(Fault::Fault)
+            .into_bits();
+
+        let mut e = enter();
+        e.extend(poll(ProchotStatus::RESET, fault, 12600));
+        e.extend(restore());
+        e.extend(enter());
+        e.extend(poll(ProchotStatus::RESET, ChargerStatus0::RESET, 12600));
+        e.extend(restore());
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = LearnConfig {
+            max_polls: 1,
+            ..LearnConfig::new(9000)
+        };
+
+        let report = bq.run_learn_cycle(&mut NoopDelay, config).await.unwrap();
+        assert_eq!(report.stop, LearnStop::Fault);
+
+        assert_eq!(bq.run_learn_cycle(&mut NoopDelay, config).await, Err(Error::Timeout));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -13,55 +13,209 @@
 #![cfg_attr(not(test), no_std)]
 
 use embedded_hal_async::i2c::I2c;
+use interface::{I2cInterface, RegisterInterface};
+use power_path::UnsafeTransition;
 use registers::*;
 
+pub mod autotune;
+pub mod cells;
+#[cfg(feature = "embedded-batteries")]
+pub mod charger;
+pub mod comparator;
+pub mod coulomb;
+pub mod discharge;
+pub mod frs;
+pub mod ico;
+pub mod input;
+pub mod interface;
+pub mod jeita;
+pub mod learn;
+pub mod low_power;
+pub mod peak_power;
+pub mod phases;
+pub mod power_flow;
+pub mod power_path;
+pub mod psys;
 pub mod registers;
+pub mod sbs;
+pub mod thermistor;
+pub mod vap;
+
+/// Errors reported by the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// The register interface, normally the I2C bus, reported an
+    /// error.
+    Interface(E),
+
+    /// A register read in strict mode held a reserved or invalid
+    /// field encoding.
+    InvalidField(InvalidField),
+
+    /// The input source contract exceeds what the charger can take.
+    UnsupportedInput,
+
+    /// The charger didn't reach the expected state in time.
+    Timeout,
+
+    /// A requested setting is outside what the charger supports.
+    OutOfRange,
+
+    /// A power path transition was refused because it is unsafe in
+    /// the charger's current state.
+    UnsafeTransition(UnsafeTransition),
+}
 
-pub struct Bq2577x<I2C: I2c> {
-    /// The concrete I2C bus instance
-    i2c: I2C,
+pub struct Bq2577x<IFACE: RegisterInterface> {
+    /// The register transport
+    iface: IFACE,
 
-    /// This device's I2C address
-    addr: u8,
-}
+    /// Reject reserved field encodings on register reads
+    strict: bool,
+
+    /// Battery pack cell count, configured or detected
+    cells: Option<cells::CellCount>,
 
-impl<I2C: I2c> Bq2577x<I2C> {
-    const ADDR: u8 = 0x09;
+    /// Configuration to restore on leaving low power mode
+    low_power: Option<low_power::LowPowerSaved>,
+
+    /// ProchotStatus events read, and so cleared, but not yet taken
+    prochot_pending: u16,
+
+    /// ChargeOption4 events read, and so cleared, but not yet taken
+    option4_pending: u16,
+}
 
+impl<I2C: I2c> Bq2577x<I2cInterface<I2C>> {
     pub fn new(i2c: I2C) -> Self {
-        Self { i2c, addr: Self::ADDR }
+        Self::with_interface(I2cInterface::new(i2c))
     }
 
     pub fn destroy(self) -> I2C {
-        self.i2c
+        self.iface.destroy()
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Creates a driver reaching the charger through `iface`.
+    pub fn with_interface(iface: IFACE) -> Self {
+        Self {
+            iface,
+            strict: false,
+            cells: None,
+            low_power: None,
+            prochot_pending: 0,
+            option4_pending: 0,
+        }
+    }
+
+    pub fn into_interface(self) -> IFACE {
+        self.iface
+    }
+
+    /// Enables or disables strict decoding. In strict mode, register
+    /// reads fail with [`Error::InvalidField`] instead of folding
+    /// reserved field encodings into valid variants.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns whether strict decoding is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Reads register `R`, honouring strict decoding.
+    pub async fn read<R: Readable>(&mut self) -> Result<R, Error<IFACE::Error>> {
+        let value = self.iface.read_word(R::ADDR).await.map_err(Error::Interface)?;
+
+        if self.strict {
+            R::try_from_bits(value).map_err(Error::InvalidField)
+        } else {
+            Ok(R::from_bits(value))
+        }
+    }
+
+    /// Writes register `R`.
+    pub async fn write<R: Writable>(&mut self, reg: R) -> Result<(), Error<IFACE::Error>> {
+        self.iface
+            .write_word(R::ADDR, reg.into_bits())
+            .await
+            .map_err(Error::Interface)
+    }
+
+    /// Returns the ProchotStatus events latched since they were last
+    /// taken, and clears them.
+    ///
+    /// The events in bits 7:0 are cleared when the register is read, and
+    /// several features of this driver watch them. They keep what they
+    /// read for each other, so read the register through this method
+    /// rather than [`Self::prochot_status`], which loses events the
+    /// driver hasn't taken yet.
+    pub async fn take_prochot_status(&mut self) -> Result<ProchotStatus, Error<IFACE::Error>> {
+        self.prochot_take(ProchotStatus::from_bits(PROCHOT_EVENTS)).await
     }
 
-    async fn read(&mut self, reg: u8) -> Result<u16, I2C::Error> {
-        let mut bytes = [0; 2];
-        self.i2c.write_read(self.addr, &[reg], &mut bytes).await?;
+    /// Reads ProchotStatus, keeping the events it clears pending.
+    pub(crate) async fn prochot_read(&mut self) -> Result<ProchotStatus, Error<IFACE::Error>> {
+        let status = self.prochot_status().await?;
+        self.prochot_pending |= status.into_bits() & PROCHOT_EVENTS;
 
-        Ok(u16::from_be_bytes(bytes))
+        Ok(status)
     }
 
-    async fn write(&mut self, reg: u8, value: u16) -> Result<(), I2C::Error> {
-        let mut bytes = [0u8; 3];
-        let content = value.to_be_bytes();
+    /// Reads ProchotStatus with the pending events merged in, and takes
+    /// the events set in `take`.
+    pub(crate) async fn prochot_take(&mut self, take: ProchotStatus) -> Result<ProchotStatus, Error<IFACE::Error>> {
+        let status = self.prochot_read().await?;
+        let merged = ProchotStatus::from_bits(status.into_bits() | self.prochot_pending);
+        self.prochot_pending &= !take.into_bits();
 
-        bytes[0] = reg;
-        bytes[1] = content[0];
-        bytes[2] = content[1];
+        Ok(merged)
+    }
+
+    /// Returns ChargeOption4 with the IDCHG2 and VBUS_VAP events latched
+    /// since they were last taken, and clears them.
+    ///
+    /// Like the ProchotStatus events, these are cleared when the
+    /// register is read, so read it through this method rather than
+    /// [`Self::charge_option4`].
+    pub async fn take_charge_option4(&mut self) -> Result<ChargeOption4, Error<IFACE::Error>> {
+        self.option4_take(ChargeOption4::from_bits(OPTION4_EVENTS)).await
+    }
+
+    /// Reads ChargeOption4, keeping the events it clears pending.
+    pub(crate) async fn option4_read(&mut self) -> Result<ChargeOption4, Error<IFACE::Error>> {
+        let option4 = self.charge_option4().await?;
+        self.option4_pending |= option4.into_bits() & OPTION4_EVENTS;
+
+        Ok(option4)
+    }
 
-        self.i2c.write(self.addr, &[reg]).await?;
+    /// Reads ChargeOption4 with the pending events merged in, and takes
+    /// the events set in `take`.
+    pub(crate) async fn option4_take(&mut self, take: ChargeOption4) -> Result<ChargeOption4, Error<IFACE::Error>> {
+        let option4 = self.option4_read().await?;
+        let merged = ChargeOption4::from_bits(option4.into_bits() | self.option4_pending);
+        self.option4_pending &= !take.into_bits();
 
-        Ok(())
+        Ok(merged)
     }
 }
 
+/// ProchotStatus events, latched until the register is read.
+const PROCHOT_EVENTS: u16 = 0x00ff;
+
+/// ChargeOption4 events, STAT_IDCHG2 and STAT_VBUS_VAP, latched until the
+/// register is read.
+const OPTION4_EVENTS: u16 = 0x0102;
+
 macro_rules! impl_read {
     ($method:ident, $r:ty) => {
-        impl<I2C: I2c> Bq2577x<I2C> {
-            pub async fn $method(&mut self) -> Result<$r, I2C::Error> {
-                Ok(<$r>::from_bits(self.read(<$r>::addr()).await?))
+        impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+            pub async fn $method(&mut self) -> Result<$r, Error<IFACE::Error>> {
+                self.read::<$r>().await
             }
         }
     };
@@ -69,9 +223,9 @@ macro_rules! impl_read {
 
 macro_rules! impl_write {
     ($method:ident, $r:ty) => {
-        impl<I2C: I2c> Bq2577x<I2C> {
-            pub async fn $method(&mut self, reg: $r) -> Result<(), I2C::Error> {
-                self.write(<$r>::addr(), reg.into_bits()).await
+        impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+            pub async fn $method(&mut self, reg: $r) -> Result<(), Error<IFACE::Error>> {
+                self.write(reg).await
             }
         }
     };
@@ -170,11 +324,22 @@ impl_read!(manufacturer, Manufacturer);
 impl_read!(device, Device);
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
 
     use super::*;
 
+    /// Expects a read of register `R` returning `value`.
+    pub(crate) fn read<R: Register>(value: u16) -> Transaction {
+        Transaction::write_read(0x09, vec![R::ADDR], value.to_be_bytes().to_vec())
+    }
+
+    /// Expects a write of `value` to register `R`.
+    pub(crate) fn write<R: Register>(value: u16) -> Transaction {
+        let [hi, lo] = value.to_be_bytes();
+        Transaction::write(0x09, vec![R::ADDR, hi, lo])
+    }
+
     macro_rules! test_reset {
         ($method:ident, $r:ty, $v:expr) => {
             let value = $v as u16;
@@ -191,6 +356,7 @@ mod tests {
 
             let reset = result.unwrap();
             assert_eq!(reset.into_bits(), value);
+            assert_eq!(<$r as Register>::RESET, value);
 
             let mut mock = bq.destroy();
             mock.done();
@@ -237,4 +403,62 @@ mod tests {
         test_reset!(manufacturer, Manufacturer, 0x0040);
         test_reset!(device, Device, 0x000a);
     }
+
+    #[tokio::test]
+    async fn test_strict_decoding() {
+        // CHRG_STAT = 101b is reserved
+        let e = vec![
+            Transaction::write_read(0x09, vec![ChargerStatus0::addr()], vec![0xa0, 0x00]),
+            Transaction::write_read(0x09, vec![ChargerStatus0::addr()], vec![0xa0, 0x00]),
+            Transaction::write_read(0x09, vec![ChargerStatus0::addr()], vec![0xe0, 0x00]),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let status = bq.charger_status0().await.unwrap();
+        assert_eq!(status.chrg_stat(), ChrgStat::ChargeTerminationDone);
+
+        bq.set_strict(true);
+        assert_eq!(
+            bq.charger_status0().await,
+            Err(Error::InvalidField(InvalidField {
+                register: ChargerStatus0::addr(),
+                field: "chrg_stat",
+                value: 5,
+            }))
+        );
+
+        let status = bq.charger_status0().await.unwrap();
+        assert_eq!(status.chrg_stat(), ChrgStat::ChargeTerminationDone);
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_generic_access() {
+        let e = vec![
+            Transaction::write_read(0x09, vec![ChargeCurrent::ADDR], vec![0x01, 0x00]),
+            Transaction::write(0x09, vec![ChargeCurrent::ADDR, 0x02, 0x00]),
+            Transaction::write_read(0x09, vec![AdcVbus::ADDR], vec![0x17, 0x70]),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let current = bq.read::<ChargeCurrent>().await.unwrap();
+        assert_eq!(current.current(), 32);
+
+        bq.write(current.with_current(64)).await.unwrap();
+
+        let vbus: AdcVbus = bq.read().await.unwrap();
+        assert_eq!(vbus.voltage(), 0x1770);
+
+        assert_eq!(ChargeCurrent::ACCESS, Access::ReadWrite);
+        assert_eq!(AdcVbus::ACCESS, Access::ReadOnly);
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
 }
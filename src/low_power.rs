@@ -0,0 +1,293 @@
+//! Battery only low power mode, for lowest quiescent current during long
+//! standby.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::power_path::UnsafeTransition;
+use crate::registers::{
+    AdcEn, AdcOption, ChargeOption0, ChargeOption1, ChargeOption5, EnAdcCmpin, EnAdcIbat, EnAdcIin, EnAdcPsys,
+    EnAdcVbat, EnAdcVbus, EnAdcVsys, EnIbat, EnLwPwrCmp, LowPwrMode, PsysConfig, RegnExt, RegnLwPwr,
+};
+use crate::{Bq2577x, Error};
+
+/// Blocks kept running in low power mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LowPowerConfig {
+    /// Keep the independent comparator enabled.
+    pub comparator: bool,
+
+    /// Keep REGN on, with its current capability scaled down to 5mA.
+    pub regn: bool,
+
+    /// Overdrive REGN from an external 5V supply.
+    pub regn_ext: bool,
+}
+
+/// Configuration saved on entering low power mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub(crate) struct LowPowerSaved {
+    option0: ChargeOption0,
+    option1: ChargeOption1,
+    option5: ChargeOption5,
+    adc_option: AdcOption,
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Enters battery only low power mode. The ADC and the IBAT and
+    /// PSYS buffers don't work there, so they are disabled first.
+    ///
+    /// The configuration in place before the first call is saved and
+    /// put back by [`Self::exit_low_power`]; calling this again while
+    /// in low power mode only changes `config`.
+    ///
+    /// Refused with [`crate::power_path::UnsafeTransition::AdapterPresent`]
+    /// while an adapter is present, as measured by
+    /// [`Self::measure_adapter_present`]. If a write fails part way, the
+    /// configuration is still saved so [`Self::exit_low_power`] can put
+    /// it back.
+    pub async fn enter_low_power<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        config: LowPowerConfig,
+    ) -> Result<(), Error<IFACE::Error>> {
+        let saved = match self.low_power {
+            Some(saved) => saved,
+            None => {
+                if self.measure_adapter_present(delay).await? {
+                    return Err(Error::UnsafeTransition(UnsafeTransition::AdapterPresent));
+                }
+
+                let saved = LowPowerSaved {
+                    option0: self.charge_option0().await?,
+                    option1: self.charge_option1().await?,
+                    option5: self.charge_option5().await?,
+                    adc_option: self.adc_option().await?,
+                };
+
+                self.low_power = Some(saved);
+
+                saved
+            }
+        };
+
+        self.set_adc_option(
+            saved
+                .adc_option
+                .with_adc_en(AdcEn::Disable)
+                .with_en_adc_vbat(EnAdcVbat::Disable)
+                .with_en_adc_vsys(EnAdcVsys::Disable)
+                .with_en_adc_ibat(EnAdcIbat::Disable)
+                .with_en_adc_iin(EnAdcIin::Disable)
+                .with_en_adc_psys(EnAdcPsys::Disable)
+                .with_en_adc_vbus(EnAdcVbus::Disable)
+                .with_en_adc_cmpin(EnAdcCmpin::Disable),
+        )
+        .await?;
+
+        let comparator = if config.comparator {
+            EnLwPwrCmp::Enable
+        } else {
+            EnLwPwrCmp::Disable
+        };
+
+        self.set_charge_option1(
+            saved
+                .option1
+                .with_en_ibat(EnIbat::Disable)
+                .with_psys_config(PsysConfig::Off)
+                .with_en_lwpwr_cmp(comparator),
+        )
+        .await?;
+
+        let regn = if config.regn {
+            RegnLwPwr::Enable
+        } else {
+            RegnLwPwr::Disable
+        };
+
+        let regn_ext = if config.regn_ext {
+            RegnExt::Enable
+        } else {
+            RegnExt::Disable
+        };
+
+        self.set_charge_option5(saved.option5.with_en_reg_lwpwr(regn).with_regn_ext(regn_ext))
+            .await?;
+
+        self.set_charge_option0(saved.option0.with_en_lwpwr(LowPwrMode::Enable))
+            .await
+    }
+
+    /// Leaves low power mode and restores the configuration saved by
+    /// [`Self::enter_low_power`]. Does nothing if low power mode wasn't
+    /// entered through this driver. If a write fails, the configuration
+    /// stays saved so this can be retried.
+    pub async fn exit_low_power(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let Some(saved) = self.low_power else {
+            return Ok(());
+        };
+
+        self.set_charge_option0(saved.option0).await?;
+        self.set_charge_option5(saved.option5).await?;
+        self.set_charge_option1(saved.option1).await?;
+        self.set_adc_option(saved.adc_option).await?;
+
+        self.low_power = None;
+
+        Ok(())
+    }
+
+    /// Returns whether low power mode was entered through this driver.
+    pub fn is_low_power(&self) -> bool {
+        self.low_power.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_async::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{AdcRate, AdcVbus, Register};
+    use crate::tests::{read, write};
+
+    const CONFIG: LowPowerConfig = LowPowerConfig {
+        comparator: true,
+        regn: true,
+        regn_ext: false,
+    };
+
+    fn option1() -> ChargeOption1 {
+        ChargeOption1::from_bits(ChargeOption1::RESET).with_en_ibat(EnIbat::Enable)
+    }
+
+    fn option1_lwpwr() -> ChargeOption1 {
+        option1()
+            .with_en_ibat(EnIbat::Disable)
+            .with_psys_config(PsysConfig::Off)
+            .with_en_lwpwr_cmp(EnLwPwrCmp::Enable)
+    }
+
+    fn option0() -> ChargeOption0 {
+        ChargeOption0::from_bits(ChargeOption0::RESET).with_en_lwpwr(LowPwrMode::Disable)
+    }
+
+    fn option5() -> ChargeOption5 {
+        ChargeOption5::from_bits(ChargeOption5::RESET)
+    }
+
+    #[tokio::test]
+    async fn test_low_power() {
+        let adc_option = AdcOption::from_bits(AdcOption::RESET)
+            .with_adc_en(AdcEn::Enable)
+            .with_adc_rate(AdcRate::Continuous)
+            .with_en_adc_vbus(EnAdcVbus::Enable)
+            .with_en_adc_vbat(EnAdcVbat::Enable);
+        let adc_off = adc_option
+            .with_adc_en(AdcEn::Disable)
+            .with_en_adc_vbus(EnAdcVbus::Disable)
+            .with_en_adc_vbat(EnAdcVbat::Disable);
+        let option5_lwpwr = option5()
+            .with_en_reg_lwpwr(RegnLwPwr::Enable)
+            .with_regn_ext(RegnExt::Disable);
+
+        let e = vec![
+            read::<AdcOption>(adc_option.into_bits()),
+            read::<AdcVbus>(AdcVbus::new().with_voltage(10000).into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            read::<AdcVbus>(0),
+            read::<ChargeOption0>(option0().into_bits()),
+            read::<ChargeOption1>(option1().into_bits()),
+            read::<ChargeOption5>(option5().into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            write::<AdcOption>(adc_off.into_bits()),
+            write::<ChargeOption1>(option1_lwpwr().into_bits()),
+            write::<ChargeOption5>(option5_lwpwr.into_bits()),
+            write::<ChargeOption0>(option0().with_en_lwpwr(LowPwrMode::Enable).into_bits()),
+            write::<ChargeOption0>(option0().into_bits()),
+            write::<ChargeOption5>(option5().into_bits()),
+            write::<ChargeOption1>(option1().into_bits()),
+            write::<AdcOption>(adc_option.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(
+            bq.enter_low_power(&mut NoopDelay, CONFIG).await,
+            Err(Error::UnsafeTransition(UnsafeTransition::AdapterPresent))
+        );
+        assert!(!bq.is_low_power());
+
+        bq.enter_low_power(&mut NoopDelay, CONFIG).await.unwrap();
+        assert!(bq.is_low_power());
+
+        bq.exit_low_power().await.unwrap();
+        assert!(!bq.is_low_power());
+        // nothing left to restore
+        bq.exit_low_power().await.unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_low_power_from_por() {
+        // POR leaves the ADC off, so VBUS is measured with a one-shot
+        // conversion
+        let adc_option = AdcOption::from_bits(AdcOption::RESET);
+        let one_shot = adc_option
+            .with_en_adc_vbus(EnAdcVbus::Enable)
+            .with_adc_rate(AdcRate::OneShot)
+            .with_adc_en(AdcEn::Enable);
+        let adc_off = adc_option
+            .with_adc_en(AdcEn::Disable)
+            .with_en_adc_vbat(EnAdcVbat::Disable)
+            .with_en_adc_vsys(EnAdcVsys::Disable)
+            .with_en_adc_ibat(EnAdcIbat::Disable)
+            .with_en_adc_iin(EnAdcIin::Disable)
+            .with_en_adc_psys(EnAdcPsys::Disable)
+            .with_en_adc_vbus(EnAdcVbus::Disable)
+            .with_en_adc_cmpin(EnAdcCmpin::Disable);
+
+        let e = vec![
+            read::<AdcOption>(adc_option.into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            write::<AdcOption>(one_shot.into_bits()),
+            read::<AdcOption>(one_shot.with_adc_en(AdcEn::Disable).into_bits()),
+            write::<AdcOption>(adc_option.with_adc_en(AdcEn::Disable).into_bits()),
+            read::<AdcVbus>(0),
+            read::<ChargeOption0>(option0().into_bits()),
+            read::<ChargeOption1>(option1().into_bits()),
+            read::<ChargeOption5>(option5().into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            write::<AdcOption>(adc_off.into_bits()),
+            write::<ChargeOption1>(option1_lwpwr().into_bits()).with_error(ErrorKind::Other),
+            // the failed entry is still undone
+            write::<ChargeOption0>(option0().into_bits()),
+            write::<ChargeOption5>(option5().into_bits()),
+            write::<ChargeOption1>(option1().into_bits()),
+            write::<AdcOption>(adc_option.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(
+            bq.enter_low_power(&mut NoopDelay, CONFIG).await,
+            Err(Error::Interface(ErrorKind::Other))
+        );
+        assert!(bq.is_low_power());
+
+        bq.exit_low_power().await.unwrap();
+        assert!(!bq.is_low_power());
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,275 @@
+//! Peak power mode, which lets the system briefly draw more than the
+//! adapter limit by supplementing it from the battery.
+
+use crate::interface::RegisterInterface;
+use crate::registers::{
+    ChargeOption2, EnPkPwrIinDpm, EnPkPwrVsys, Ilim2Vth, PkPwrTmax, PkPwrTovldDeg, StatPkPwrOvld, StatPkPwrRelax,
+};
+use crate::{Bq2577x, Error};
+
+/// Peak power mode configuration in real units. Values that don't
+/// match a charger setting are rounded down to the nearest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PeakPowerConfig {
+    /// Overload current level (ILIM2) as a percentage of IIN_DPM,
+    /// 110% to 450%.
+    pub overload_percent: u16,
+
+    /// Time the input may stay overloaded, 1ms to 10ms.
+    pub overload_ms: u16,
+
+    /// Overload and relax cycle time, 20ms to 1s.
+    pub cycle_ms: u16,
+
+    /// Enter peak power mode on input current overshoot.
+    pub trigger_iin_dpm: bool,
+
+    /// Enter peak power mode on system voltage undershoot.
+    pub trigger_vsys: bool,
+}
+
+impl Default for PeakPowerConfig {
+    fn default() -> Self {
+        Self {
+            overload_percent: 150,
+            overload_ms: 1,
+            cycle_ms: 20,
+            trigger_iin_dpm: true,
+            trigger_vsys: true,
+        }
+    }
+}
+
+/// Peak power mode phase, as reported by the status bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PeakPowerState {
+    Idle,
+    Overload,
+    Relax,
+}
+
+impl PeakPowerState {
+    pub const fn from_status(reg: ChargeOption2) -> Self {
+        match (reg.stat_pkpwr_ovld(), reg.stat_pkpwr_relax()) {
+            (StatPkPwrOvld::InPeak, _) => Self::Overload,
+            (_, StatPkPwrRelax::InRelaxation) => Self::Relax,
+            _ => Self::Idle,
+        }
+    }
+}
+
+/// Accumulates how often and for how long the charger went through
+/// overload and relax cycles, from periodically sampled status bits.
+///
+/// Time between two samples is attributed to the state seen in the
+/// earlier one, so accuracy depends on the sampling rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PeakPowerMonitor {
+    last: Option<(PeakPowerState, u64)>,
+
+    /// Number of times an overload cycle was entered.
+    pub overload_count: u32,
+
+    /// Number of times a relax cycle was entered.
+    pub relax_count: u32,
+
+    /// Total time spent in overload, in milliseconds.
+    pub overload_ms: u64,
+
+    /// Total time spent in relax, in milliseconds.
+    pub relax_ms: u64,
+}
+
+impl PeakPowerMonitor {
+    pub const fn new() -> Self {
+        Self {
+            last: None,
+            overload_count: 0,
+            relax_count: 0,
+            overload_ms: 0,
+            relax_ms: 0,
+        }
+    }
+
+    /// Records a sample of the status bits taken at `now_ms` and
+    /// returns the current state.
+    pub fn sample(&mut self, reg: ChargeOption2, now_ms: u64) -> PeakPowerState {
+        let state = PeakPowerState::from_status(reg);
+        let (previous, since) = self.last.unwrap_or((PeakPowerState::Idle, now_ms));
+        let elapsed = now_ms.saturating_sub(since);
+
+        match previous {
+            PeakPowerState::Overload => self.overload_ms += elapsed,
+            PeakPowerState::Relax => self.relax_ms += elapsed,
+            PeakPowerState::Idle => {}
+        }
+
+        if state != previous {
+            match state {
+                PeakPowerState::Overload => self.overload_count += 1,
+                PeakPowerState::Relax => self.relax_count += 1,
+                PeakPowerState::Idle => {}
+            }
+        }
+
+        self.last = Some((state, now_ms));
+
+        state
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Configures and enables peak power mode. Fails with
+    /// [`Error::OutOfRange`] if a value is below the charger's lowest
+    /// setting.
+    ///
+    /// The charger clears the triggers on adapter removal, so this
+    /// must be called again after every adapter insertion.
+    pub async fn configure_peak_power(&mut self, config: PeakPowerConfig) -> Result<(), Error<IFACE::Error>> {
+        let ilim2 = Ilim2Vth::from_percent(config.overload_percent).ok_or(Error::OutOfRange)?;
+        let tovld = PkPwrTovldDeg::from_millis(config.overload_ms).ok_or(Error::OutOfRange)?;
+        let tmax = PkPwrTmax::from_millis(config.cycle_ms).ok_or(Error::OutOfRange)?;
+
+        let prochot0 = self.prochot_option0().await?;
+        self.set_prochot_option0(prochot0.with_ilim2_vth(ilim2)).await?;
+
+        let iin_dpm = if config.trigger_iin_dpm {
+            EnPkPwrIinDpm::Enable
+        } else {
+            EnPkPwrIinDpm::Disable
+        };
+
+        let vsys = if config.trigger_vsys {
+            EnPkPwrVsys::Enable
+        } else {
+            EnPkPwrVsys::Disable
+        };
+
+        let option2 = self.charge_option2().await?;
+        self.set_charge_option2(
+            option2
+                .with_pkpwr_tovld_deg(tovld)
+                .with_pkpwr_tmax(tmax)
+                .with_en_pkpwr_iin_dpm(iin_dpm)
+                .with_en_pkpwr_vsys(vsys),
+        )
+        .await
+    }
+
+    /// Disables both peak power mode triggers.
+    pub async fn disable_peak_power(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option2 = self.charge_option2().await?;
+        self.set_charge_option2(
+            option2
+                .with_en_pkpwr_iin_dpm(EnPkPwrIinDpm::Disable)
+                .with_en_pkpwr_vsys(EnPkPwrVsys::Disable),
+        )
+        .await
+    }
+
+    /// Samples the peak power status bits into `monitor`.
+    pub async fn sample_peak_power(
+        &mut self,
+        monitor: &mut PeakPowerMonitor,
+        now_ms: u64,
+    ) -> Result<PeakPowerState, Error<IFACE::Error>> {
+        let option2 = self.charge_option2().await?;
+
+        Ok(monitor.sample(option2, now_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ProchotOption0, Register};
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_peak_power_units() {
+        assert_eq!(Ilim2Vth::from_percent(100), None);
+        assert_eq!(Ilim2Vth::from_percent(110), Some(Ilim2Vth::Percent110));
+        assert_eq!(Ilim2Vth::from_percent(152), Some(Ilim2Vth::Percent150));
+        assert_eq!(Ilim2Vth::from_percent(240), Some(Ilim2Vth::Percent230));
+        assert_eq!(Ilim2Vth::from_percent(300), Some(Ilim2Vth::Percent300));
+        assert_eq!(Ilim2Vth::from_percent(1000), Some(Ilim2Vth::Percent450));
+
+        for code in 1..=30 {
+            let threshold = Ilim2Vth::try_from_bits(code).unwrap();
+            assert_eq!(Ilim2Vth::from_percent(threshold.percent()), Some(threshold));
+        }
+
+        assert_eq!(PkPwrTovldDeg::from_millis(0), None);
+        assert_eq!(PkPwrTovldDeg::from_millis(7), Some(PkPwrTovldDeg::Time5ms));
+        assert_eq!(PkPwrTmax::from_millis(10), None);
+        assert_eq!(PkPwrTmax::from_millis(500), Some(PkPwrTmax::Time80ms));
+        assert_eq!(PkPwrTmax::from_millis(1000).map(PkPwrTmax::millis), Some(1000));
+    }
+
+    #[test]
+    fn test_peak_power_monitor() {
+        let idle = ChargeOption2::new();
+        let overload = idle.with_stat_pkpwr_ovld(StatPkPwrOvld::InPeak);
+        let relax = idle.with_stat_pkpwr_relax(StatPkPwrRelax::InRelaxation);
+
+        let mut monitor = PeakPowerMonitor::new();
+
+        assert_eq!(monitor.sample(idle, 0), PeakPowerState::Idle);
+        assert_eq!(monitor.sample(overload, 10), PeakPowerState::Overload);
+        assert_eq!(monitor.sample(overload, 15), PeakPowerState::Overload);
+        assert_eq!(monitor.sample(relax, 20), PeakPowerState::Relax);
+        assert_eq!(monitor.sample(overload, 40), PeakPowerState::Overload);
+        assert_eq!(monitor.sample(idle, 50), PeakPowerState::Idle);
+
+        assert_eq!(monitor.overload_count, 2);
+        assert_eq!(monitor.relax_count, 1);
+        assert_eq!(monitor.overload_ms, 20);
+        assert_eq!(monitor.relax_ms, 20);
+    }
+
+    #[tokio::test]
+    async fn test_configure_peak_power() {
+        let prochot0 = ProchotOption0::new().with_ilim2_vth(Ilim2Vth::Percent200).into_bits();
+        let option2 = ChargeOption2::new()
+            .with_pkpwr_tovld_deg(PkPwrTovldDeg::Time5ms)
+            .with_pkpwr_tmax(PkPwrTmax::Time80ms)
+            .with_en_pkpwr_iin_dpm(EnPkPwrIinDpm::Enable)
+            .with_en_pkpwr_vsys(EnPkPwrVsys::Disable)
+            .into_bits();
+
+        let e = vec![
+            read::<ProchotOption0>(ProchotOption0::RESET),
+            write::<ProchotOption0>(prochot0),
+            read::<ChargeOption2>(ChargeOption2::RESET),
+            write::<ChargeOption2>(option2),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = PeakPowerConfig {
+            overload_percent: 200,
+            overload_ms: 5,
+            cycle_ms: 80,
+            trigger_iin_dpm: true,
+            trigger_vsys: false,
+        };
+
+        bq.configure_peak_power(config).await.unwrap();
+
+        let config = PeakPowerConfig {
+            overload_percent: 100,
+            ..config
+        };
+
+        assert_eq!(bq.configure_peak_power(config).await, Err(Error::OutOfRange));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
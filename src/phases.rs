@@ -0,0 +1,217 @@
+//! Dual phase buck converter control, for tuning light-load efficiency.
+//!
+//! The charger doesn't report which phases are switching at any given
+//! moment. Despite their names, the `*drv*_stat` fields of
+//! [`crate::registers::GateDrive`] are gate drive strength settings, not
+//! status. What can be reported is whether the MODE pin allows dual
+//! phase operation and how the transition is configured.
+
+use crate::interface::RegisterInterface;
+use crate::registers::{BuckTransitionThreshold, ChargeOption5, ForceSingle, HighDutyBuck, PhAddDeg, PhDropDeg};
+use crate::{Bq2577x, Error};
+
+/// Phase selection in buck mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PhaseMode {
+    /// Single phase only.
+    ForceSingle,
+
+    /// Dual phase at any load.
+    ForceDual,
+
+    /// Add the second phase above a load current, 3A to 9A, and drop
+    /// it again 1A below.
+    Auto { threshold_a: u8 },
+}
+
+impl PhaseMode {
+    const fn from_register(reg: ChargeOption5) -> Self {
+        match (reg.force_single(), reg.single_dual_trans_th().amps()) {
+            (ForceSingle::Enable, _) => Self::ForceSingle,
+            (_, None) => Self::ForceDual,
+            (_, Some(threshold_a)) => Self::Auto { threshold_a },
+        }
+    }
+}
+
+/// Phase control configuration in real units. Deglitch times that don't
+/// match a charger setting are rounded down to the nearest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PhaseConfig {
+    pub mode: PhaseMode,
+
+    /// Time the load must stay above the threshold before adding the
+    /// second phase, in nanoseconds.
+    pub add_deglitch_ns: u32,
+
+    /// Time the load must stay below the threshold before dropping the
+    /// second phase, in microseconds.
+    pub drop_deglitch_us: u32,
+
+    /// Keep Q4 on instead of boost switching at high buck duty cycles.
+    pub high_duty_buck: bool,
+}
+
+impl Default for PhaseConfig {
+    fn default() -> Self {
+        Self {
+            mode: PhaseMode::Auto { threshold_a: 6 },
+            add_deglitch_ns: 5500,
+            drop_deglitch_us: 1500,
+            high_duty_buck: false,
+        }
+    }
+}
+
+/// Phase configuration in effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PhaseStatus {
+    /// The MODE pin selects quasi dual phase operation. Without it the
+    /// converter always runs a single phase and [`PhaseStatus::mode`]
+    /// has no effect.
+    pub quasi_dual: bool,
+
+    /// Configured phase selection.
+    pub mode: PhaseMode,
+
+    /// Configured phase adding deglitch time.
+    pub add_deglitch: PhAddDeg,
+
+    /// Configured phase dropping deglitch time.
+    pub drop_deglitch: PhDropDeg,
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Configures phase selection. Fails with [`Error::OutOfRange`] if
+    /// the threshold is outside 3A to 9A or a deglitch time is below the
+    /// charger's shortest one.
+    pub async fn configure_phases(&mut self, config: PhaseConfig) -> Result<(), Error<IFACE::Error>> {
+        let add = PhAddDeg::from_nanos(config.add_deglitch_ns).ok_or(Error::OutOfRange)?;
+        let drop = PhDropDeg::from_micros(config.drop_deglitch_us).ok_or(Error::OutOfRange)?;
+
+        let option5 = self.charge_option5().await?;
+
+        let option5 = match config.mode {
+            PhaseMode::ForceSingle => option5.with_force_single(ForceSingle::Enable),
+            PhaseMode::ForceDual => option5
+                .with_force_single(ForceSingle::Disable)
+                .with_single_dual_trans_th(BuckTransitionThreshold::ForceDual),
+            PhaseMode::Auto { threshold_a } => option5
+                .with_force_single(ForceSingle::Disable)
+                .with_single_dual_trans_th(BuckTransitionThreshold::from_amps(threshold_a).ok_or(Error::OutOfRange)?),
+        };
+
+        let high_duty_buck = if config.high_duty_buck {
+            HighDutyBuck::Enable
+        } else {
+            HighDutyBuck::Disable
+        };
+
+        self.set_charge_option5(
+            option5
+                .with_ph_add_deg(add)
+                .with_ph_drop_deg(drop)
+                .with_high_duty_buck(high_duty_buck),
+        )
+        .await
+    }
+
+    /// Reports the phase configuration in effect. This is not which
+    /// phases are switching right now: the charger doesn't report that,
+    /// see the [module documentation](crate::phases).
+    pub async fn phase_status(&mut self) -> Result<PhaseStatus, Error<IFACE::Error>> {
+        let quasi_dual = self.charger_status0().await?.mode_stat().is_quasi_dual();
+        let option5 = self.charge_option5().await?;
+
+        Ok(PhaseStatus {
+            quasi_dual,
+            mode: PhaseMode::from_register(option5),
+            add_deglitch: option5.ph_add_deg(),
+            drop_deglitch: option5.ph_drop_deg(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargerStatus0, ModeStat, Register};
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_phase_units() {
+        assert_eq!(PhAddDeg::from_nanos(1000), None);
+        assert_eq!(PhAddDeg::from_nanos(10_000), Some(PhAddDeg::Time5500ns));
+        assert_eq!(PhDropDeg::from_micros(50), None);
+        assert_eq!(PhDropDeg::from_micros(100_000), Some(PhDropDeg::Time94000us));
+        assert_eq!(BuckTransitionThreshold::from_amps(2), None);
+
+        for amps in 3..=9 {
+            assert_eq!(BuckTransitionThreshold::from_amps(amps).unwrap().amps(), Some(amps));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_configure_phases() {
+        let option5 = ChargeOption5::from_bits(ChargeOption5::RESET);
+        let auto = option5
+            .with_single_dual_trans_th(BuckTransitionThreshold::Current4A)
+            .with_ph_add_deg(PhAddDeg::Time20000ns)
+            .with_ph_drop_deg(PhDropDeg::Time11000us);
+        let status = ChargerStatus0::from_bits(ChargerStatus0::RESET).with_mode_stat(ModeStat::NormalCompFsw600kHz);
+
+        let e = vec![
+            read::<ChargeOption5>(option5.into_bits()),
+            write::<ChargeOption5>(auto.into_bits()),
+            read::<ChargeOption5>(option5.into_bits()),
+            write::<ChargeOption5>(option5.with_force_single(ForceSingle::Enable).into_bits()),
+            read::<ChargeOption5>(option5.into_bits()),
+            read::<ChargerStatus0>(status.into_bits()),
+            read::<ChargeOption5>(auto.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = PhaseConfig {
+            mode: PhaseMode::Auto { threshold_a: 4 },
+            add_deglitch_ns: 20_000,
+            drop_deglitch_us: 11_000,
+            high_duty_buck: false,
+        };
+
+        bq.configure_phases(config).await.unwrap();
+
+        let config = PhaseConfig {
+            mode: PhaseMode::ForceSingle,
+            ..Default::default()
+        };
+
+        bq.configure_phases(config).await.unwrap();
+
+        let config = PhaseConfig {
+            mode: PhaseMode::Auto { threshold_a: 12 },
+            ..Default::default()
+        };
+
+        assert_eq!(bq.configure_phases(config).await, Err(Error::OutOfRange));
+
+        assert_eq!(
+            bq.phase_status().await,
+            Ok(PhaseStatus {
+                quasi_dual: false,
+                mode: PhaseMode::Auto { threshold_a: 4 },
+                add_deglitch: PhAddDeg::Time20000ns,
+                drop_deglitch: PhDropDeg::Time11000us,
+            })
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
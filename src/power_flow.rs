@@ -0,0 +1,362 @@
+//! Power flow snapshot: adapter, battery and system power from one ADC
+//! acquisition, with flags for readings that don't add up.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::power_path::VBUS_PRESENT_MV;
+use crate::psys::Psys;
+use crate::registers::{
+    AdcEn, AdcRate, EnAdcIbat, EnAdcIin, EnAdcPsys, EnAdcVbat, EnAdcVbus, EnAdcVsys, EnOtg, PsysConfig,
+};
+use crate::{Bq2577x, Error};
+
+/// Time between ADC_EN polls, in milliseconds.
+const ADC_POLL_MS: u32 = 5;
+
+/// Number of ADC_EN polls after which [`Error::Timeout`] is reported.
+/// Six channels at the slowest resolution take 144ms.
+const ADC_MAX_POLLS: u32 = 60;
+
+/// Currents below this are taken as ADC noise, in milliamps.
+const CURRENT_NOISE_MA: i32 = 50;
+
+/// Power differences below this are taken as ADC noise, in milliwatts.
+const POWER_NOISE_MW: i32 = 500;
+
+/// Readings that are inconsistent with each other, pointing at a stale
+/// channel, a sense resistor setting that doesn't match the board, or a
+/// hardware fault.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerFlowFlags {
+    /// Input current flows towards an adapter while OTG is disabled.
+    pub reverse_input: bool,
+
+    /// Input current or battery charge current without an adapter.
+    pub current_without_adapter: bool,
+
+    /// The battery takes more power than the adapter supplies while OTG
+    /// is disabled.
+    pub power_imbalance: bool,
+
+    /// PSYS disagrees with the power from the current and voltage
+    /// channels by more than 10%.
+    pub psys_mismatch: bool,
+}
+
+impl PowerFlowFlags {
+    /// Whether any reading is inconsistent.
+    pub const fn any(&self) -> bool {
+        self.reverse_input || self.current_without_adapter || self.power_imbalance || self.psys_mismatch
+    }
+}
+
+/// Readings from one ADC acquisition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerFlow {
+    /// Input voltage, in millivolts.
+    pub vbus_mv: i32,
+
+    /// Input current in milliamps; positive from the adapter, negative
+    /// towards VBUS in OTG mode.
+    pub iin_ma: i32,
+
+    /// System voltage, in millivolts.
+    pub vsys_mv: i32,
+
+    /// Battery voltage, in millivolts.
+    pub vbat_mv: i32,
+
+    /// Battery current in milliamps; positive when charging, negative
+    /// when discharging.
+    pub ibat_ma: i32,
+
+    /// PSYS reading in milliwatts, if requested and enabled.
+    pub psys_mw: Option<u32>,
+
+    pub flags: PowerFlowFlags,
+}
+
+impl PowerFlow {
+    /// Adapter power in milliwatts; negative when supplying VBUS in OTG
+    /// mode.
+    pub const fn adapter_mw(&self) -> i32 {
+        (self.vbus_mv as i64 * self.iin_ma as i64 / 1000) as i32
+    }
+
+    /// Battery power in milliwatts; positive when charging, negative
+    /// when discharging.
+    pub const fn battery_mw(&self) -> i32 {
+        (self.vbat_mv as i64 * self.ibat_ma as i64 / 1000) as i32
+    }
+
+    /// Power going neither into the battery nor back to the adapter, in
+    /// milliwatts: the system load plus converter losses.
+    pub const fn system_mw(&self) -> i32 {
+        self.adapter_mw() - self.battery_mw()
+    }
+
+    /// Converter efficiency for a system load measured elsewhere, e.g.
+    /// by the platform's own rails, as the charger doesn't measure the
+    /// converter output. `None` without adapter power.
+    pub fn efficiency(&self, system_load_mw: u32) -> Option<f32> {
+        let adapter_mw = self.adapter_mw();
+
+        if adapter_mw <= 0 {
+            return None;
+        }
+
+        let output_mw = system_load_mw as f32 + self.battery_mw().max(0) as f32;
+
+        Some(output_mw / adapter_mw as f32)
+    }
+
+    fn check(&mut self, otg: bool, psys_config: PsysConfig) {
+        let adapter = self.vbus_mv > VBUS_PRESENT_MV;
+
+        self.flags = PowerFlowFlags {
+            reverse_input: !otg && self.iin_ma < -CURRENT_NOISE_MA,
+            current_without_adapter: !adapter
+                && !otg
+                && (self.iin_ma.abs() > CURRENT_NOISE_MA || self.ibat_ma > CURRENT_NOISE_MA),
+            power_imbalance: adapter && !otg && self.battery_mw() > self.adapter_mw() + POWER_NOISE_MW,
+            psys_mismatch: false,
+        };
+
+        let expected_mw = match psys_config {
+            _ if otg => None,
+            PsysConfig::PbusPbat => Some(self.system_mw()),
+            PsysConfig::Pbus => Some(self.adapter_mw()),
+            PsysConfig::Off => None,
+        };
+
+        if let (Some(psys_mw), Some(expected_mw)) = (self.psys_mw, expected_mw) {
+            let error = (psys_mw as i64 - expected_mw as i64).abs();
+            let tolerance = (expected_mw.abs() as i64 / 10).max(POWER_NOISE_MW as i64);
+
+            self.flags.psys_mismatch = error > tolerance;
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Runs one ADC acquisition of the input, system and battery
+    /// channels, and PSYS if `psys` is given, and returns the resulting
+    /// power flow. The ADC configuration is restored afterwards, also
+    /// on timeout.
+    pub async fn power_flow<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        psys: Option<&Psys>,
+    ) -> Result<PowerFlow, Error<IFACE::Error>> {
+        let adc_option = self.adc_option().await?;
+
+        let en_adc_psys = if psys.is_some() {
+            EnAdcPsys::Enable
+        } else {
+            adc_option.en_adc_psys()
+        };
+
+        self.set_adc_option(
+            adc_option
+                .with_en_adc_vbus(EnAdcVbus::Enable)
+                .with_en_adc_iin(EnAdcIin::Enable)
+                .with_en_adc_vsys(EnAdcVsys::Enable)
+                .with_en_adc_vbat(EnAdcVbat::Enable)
+                .with_en_adc_ibat(EnAdcIbat::Enable)
+                .with_en_adc_psys(en_adc_psys)
+                .with_adc_rate(AdcRate::OneShot)
+                .with_adc_en(AdcEn::Enable),
+        )
+        .await?;
+
+        let result = self.power_flow_acquire(delay, psys).await;
+
+        // don't start another one-shot conversion
+        let adc_option = match adc_option.adc_rate() {
+            AdcRate::OneShot => adc_option.with_adc_en(AdcEn::Disable),
+            AdcRate::Continuous => adc_option,
+        };
+
+        self.set_adc_option(adc_option).await?;
+
+        result
+    }
+
+    /// Waits for a one-shot ADC conversion to complete.
+    pub(crate) async fn adc_one_shot_wait<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Error<IFACE::Error>> {
+        for _ in 0..ADC_MAX_POLLS {
+            delay.delay_ms(ADC_POLL_MS).await;
+
+            if self.adc_option().await?.adc_en() == AdcEn::Disable {
+                return Ok(());
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Waits for the one-shot conversion to complete and reads it.
+    async fn power_flow_acquire<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        psys: Option<&Psys>,
+    ) -> Result<PowerFlow, Error<IFACE::Error>> {
+        self.adc_one_shot_wait(delay).await?;
+
+        let option1 = self.charge_option1().await?;
+        let otg = self.charge_option3().await?.en_otg() == EnOtg::Enable;
+
+        let psys_mw = match psys {
+            Some(psys) if option1.psys_config() != PsysConfig::Off => {
+                let pin_mv = self.adc_psys().await?.millivolts();
+
+                Some(psys.milliwatts(pin_mv, option1.psys_ratio()))
+            }
+            _ => None,
+        };
+
+        let mut flow = PowerFlow {
+            vbus_mv: self.adc_vbus().await?.millivolts(),
+            iin_ma: self.adc_iin().await?.milliamps(option1.rsns_rac()),
+            vsys_mv: self.adc_vsys().await?.millivolts(),
+            vbat_mv: self.adc_vbat().await?.millivolts(),
+            ibat_ma: self.adc_ibat().await?.milliamps(option1.rsns_rsr()),
+            psys_mw,
+            flags: PowerFlowFlags::default(),
+        };
+
+        flow.check(otg, option1.psys_config());
+
+        Ok(flow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{
+        AdcIbat, AdcIin, AdcOption, AdcPsys, AdcVbat, AdcVbus, AdcVsys, ChargeOption1, ChargeOption3, PsysGain,
+        Register,
+    };
+    use crate::tests::{read, write};
+
+    fn flow(vbus_mv: i32, iin_ma: i32, ibat_ma: i32, psys_mw: Option<u32>) -> PowerFlow {
+        PowerFlow {
+            vbus_mv,
+            iin_ma,
+            vsys_mv: 12200,
+            vbat_mv: 12000,
+            ibat_ma,
+            psys_mw,
+            flags: PowerFlowFlags::default(),
+        }
+    }
+
+    #[test]
+    fn test_power_flow() {
+        // 60W from the adapter, 24W into the battery
+        let mut charging = flow(20000, 3000, 2000, Some(36_000));
+        charging.check(false, PsysConfig::PbusPbat);
+        assert_eq!(charging.adapter_mw(), 60_000);
+        assert_eq!(charging.battery_mw(), 24_000);
+        assert_eq!(charging.system_mw(), 36_000);
+        assert!(!charging.flags.any());
+
+        let efficiency = charging.efficiency(30_000).unwrap();
+        assert!((efficiency - 0.9).abs() < 0.001);
+
+        charging.psys_mw = Some(50_000);
+        charging.check(false, PsysConfig::PbusPbat);
+        assert!(charging.flags.psys_mismatch);
+
+        let mut battery_only = flow(0, 0, -1000, None);
+        battery_only.check(false, PsysConfig::Off);
+        assert_eq!(battery_only.system_mw(), 12_000);
+        assert_eq!(battery_only.efficiency(10_000), None);
+        assert!(!battery_only.flags.any());
+
+        let mut reverse = flow(20000, -500, 0, None);
+        reverse.check(false, PsysConfig::Off);
+        assert!(reverse.flags.reverse_input);
+        reverse.check(true, PsysConfig::Off);
+        assert!(!reverse.flags.any());
+
+        let mut phantom = flow(0, 0, 1000, None);
+        phantom.check(false, PsysConfig::Off);
+        assert!(phantom.flags.current_without_adapter);
+
+        let mut imbalance = flow(20000, 500, 2000, None);
+        imbalance.check(false, PsysConfig::Off);
+        assert!(imbalance.flags.power_imbalance);
+    }
+
+    #[tokio::test]
+    async fn test_power_flow_acquisition() {
+        let adc_option = AdcOption::from_bits(AdcOption::RESET);
+        let one_shot = adc_option
+            .with_en_adc_vbus(EnAdcVbus::Enable)
+            .with_en_adc_iin(EnAdcIin::Enable)
+            .with_en_adc_vsys(EnAdcVsys::Enable)
+            .with_en_adc_vbat(EnAdcVbat::Enable)
+            .with_en_adc_ibat(EnAdcIbat::Enable)
+            .with_en_adc_psys(EnAdcPsys::Enable)
+            .with_adc_rate(AdcRate::OneShot)
+            .with_adc_en(AdcEn::Enable);
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET)
+            .with_psys_ratio(PsysGain::Gain1_00)
+            .with_psys_config(PsysConfig::PbusPbat);
+
+        // POR RAC is 10mΩ: 0.5mA IIN steps
+        let e = vec![
+            read::<AdcOption>(adc_option.into_bits()),
+            write::<AdcOption>(one_shot.into_bits()),
+            read::<AdcOption>(one_shot.into_bits()),
+            read::<AdcOption>(one_shot.with_adc_en(AdcEn::Disable).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            read::<ChargeOption3>(ChargeOption3::RESET),
+            read::<AdcPsys>(AdcPsys::new().with_power(360).into_bits()),
+            read::<AdcVbus>(AdcVbus::new().with_voltage(10000).into_bits()),
+            read::<AdcIin>(AdcIin::new().with_current(6000).into_bits()),
+            read::<AdcVsys>(AdcVsys::new().with_voltage(6100).into_bits()),
+            read::<AdcVbat>(AdcVbat::new().with_voltage(12000).into_bits()),
+            read::<AdcIbat>(AdcIbat::new().with_current(2000).into_bits()),
+            write::<AdcOption>(adc_option.into_bits()),
+            read::<AdcOption>(adc_option.into_bits()),
+            write::<AdcOption>(one_shot.with_en_adc_psys(EnAdcPsys::Disable).into_bits()),
+        ]
+        .into_iter()
+        .chain((0..ADC_MAX_POLLS).map(|_| read::<AdcOption>(one_shot.into_bits())))
+        .chain([write::<AdcOption>(adc_option.into_bits())])
+        .collect::<Vec<_>>();
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        // 1µA/W into 10kΩ is 10mV/W
+        let psys = Psys::new(10_000);
+
+        assert_eq!(
+            bq.power_flow(&mut NoopDelay, Some(&psys)).await,
+            Ok(PowerFlow {
+                vbus_mv: 20000,
+                iin_ma: 3000,
+                vsys_mv: 12200,
+                vbat_mv: 12000,
+                ibat_ma: 2000,
+                psys_mw: Some(36_000),
+                flags: PowerFlowFlags::default(),
+            })
+        );
+
+        assert_eq!(bq.power_flow(&mut NoopDelay, None).await, Err(Error::Timeout));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,325 @@
+//! Power path isolation: Hi-Z mode, BATFET control and ship mode
+//! preparation, with the ordering and preconditions each one needs.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{
+    AdcEn, AdcRate, BatFetEnZ, BatFetOffHiZ, ChargeInhibit, CmpEn, EnAdcVbus, EnHiZ, EnOtg, FrcConvOff, LowPwrMode,
+    ShipDchg,
+};
+use crate::{Bq2577x, Error};
+
+/// Reason a power path transition was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UnsafeTransition {
+    /// OTG mode is enabled, so the input can't be isolated.
+    OtgActive,
+
+    /// An adapter is present.
+    AdapterPresent,
+
+    /// Adapter presence can't be determined because the VBUS ADC
+    /// channel isn't converting continuously.
+    AdapterUnknown,
+
+    /// The operation only takes effect in battery only low power mode,
+    /// which is not enabled.
+    NotLowPower,
+
+    /// The operation relies on the independent comparator, which is not
+    /// enabled.
+    ComparatorDisabled,
+}
+
+/// VBUS above this is taken as an adapter being present, in millivolts.
+pub(crate) const VBUS_PRESENT_MV: i32 = 3200;
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Reports whether an adapter is present, from the last VBUS ADC
+    /// conversion. Returns `None` if the ADC isn't converting VBUS
+    /// continuously, in which case the reading may be stale.
+    pub async fn adapter_present(&mut self) -> Result<Option<bool>, Error<IFACE::Error>> {
+        let adc_option = self.adc_option().await?;
+
+        if adc_option.adc_en() != AdcEn::Enable
+            || adc_option.adc_rate() != AdcRate::Continuous
+            || adc_option.en_adc_vbus() != EnAdcVbus::Enable
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(self.adc_vbus().await?.millivolts() > VBUS_PRESENT_MV))
+    }
+
+    /// Measures whether an adapter is present. Uses the running VBUS
+    /// conversion if the ADC converts VBUS continuously, otherwise runs
+    /// a one-shot VBUS conversion and restores the ADC configuration
+    /// afterwards, also on timeout.
+    pub async fn measure_adapter_present<D: DelayNs>(&mut self, delay: &mut D) -> Result<bool, Error<IFACE::Error>> {
+        if let Some(present) = self.adapter_present().await? {
+            return Ok(present);
+        }
+
+        let adc_option = self.adc_option().await?;
+
+        self.set_adc_option(
+            adc_option
+                .with_en_adc_vbus(EnAdcVbus::Enable)
+                .with_adc_rate(AdcRate::OneShot)
+                .with_adc_en(AdcEn::Enable),
+        )
+        .await?;
+
+        let result = self.adc_one_shot_wait(delay).await;
+
+        // don't start another one-shot conversion
+        let adc_option = match adc_option.adc_rate() {
+            AdcRate::OneShot => adc_option.with_adc_en(AdcEn::Disable),
+            AdcRate::Continuous => adc_option,
+        };
+
+        self.set_adc_option(adc_option).await?;
+        result?;
+
+        Ok(self.adc_vbus().await?.millivolts() > VBUS_PRESENT_MV)
+    }
+
+    /// Fails unless the adapter is known to be absent.
+    pub(crate) async fn require_no_adapter(&mut self) -> Result<(), Error<IFACE::Error>> {
+        match self.adapter_present().await? {
+            Some(false) => Ok(()),
+            Some(true) => Err(Error::UnsafeTransition(UnsafeTransition::AdapterPresent)),
+            None => Err(Error::UnsafeTransition(UnsafeTransition::AdapterUnknown)),
+        }
+    }
+
+    /// Enters Hi-Z mode, isolating the input so the system runs from
+    /// the battery. The BATFET is kept on so that the system stays
+    /// powered. Refused while OTG is enabled.
+    pub async fn enter_hiz(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option3 = self.charge_option3().await?;
+
+        if option3.en_otg() == EnOtg::Enable {
+            return Err(Error::UnsafeTransition(UnsafeTransition::OtgActive));
+        }
+
+        // BatFetOffHiZ::Off leaves the BATFET on during Hi-Z; both
+        // fields go out in the same write so the system is never left
+        // without a supply.
+        self.set_charge_option3(option3.with_batfetoff_hiz(BatFetOffHiZ::Off).with_en_hiz(EnHiZ::Enable))
+            .await
+    }
+
+    /// Leaves Hi-Z mode and reconnects the input.
+    pub async fn exit_hiz(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_en_hiz(EnHiZ::Disable)).await
+    }
+
+    /// Forces the BATFET off, disconnecting the battery from the
+    /// system. The charger only honours this in battery only low power
+    /// mode, so it is refused with an adapter present or low power mode
+    /// disabled.
+    ///
+    /// This removes system power.
+    pub async fn battery_disconnect(&mut self) -> Result<(), Error<IFACE::Error>> {
+        self.require_no_adapter().await?;
+
+        if self.charge_option0().await?.en_lwpwr() != LowPwrMode::Enable {
+            return Err(Error::UnsafeTransition(UnsafeTransition::NotLowPower));
+        }
+
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_batfet_enz(BatFetEnZ::ForceOff))
+            .await
+    }
+
+    /// Releases a forced BATFET off.
+    pub async fn battery_connect(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_batfet_enz(BatFetEnZ::NoForceOff))
+            .await
+    }
+
+    /// Prepares for the battery gauge to enter ship mode: inhibits
+    /// charging, then starts a 340ms discharge of the SRN node. Refused
+    /// with an adapter present or OTG enabled.
+    ///
+    /// The charger clears the discharge bit on its own; if SRN isn't
+    /// low enough for the gauge afterwards, call this again.
+    pub async fn prepare_ship_mode(&mut self) -> Result<(), Error<IFACE::Error>> {
+        if self.charge_option3().await?.en_otg() == EnOtg::Enable {
+            return Err(Error::UnsafeTransition(UnsafeTransition::OtgActive));
+        }
+
+        self.require_no_adapter().await?;
+
+        // ChargeInhibit::Disable inhibits charging
+        let option0 = self.charge_option0().await?;
+        self.set_charge_option0(option0.with_chrg_inhibit(ChargeInhibit::Disable))
+            .await?;
+
+        let option1 = self.charge_option1().await?;
+        self.set_charge_option1(option1.with_en_ship_dchg(ShipDchg::Enable))
+            .await
+    }
+
+    /// Turns the converter off, disconnecting the system from the
+    /// input, when the independent comparator trips. Enabling is
+    /// refused unless the comparator is enabled.
+    pub async fn set_comparator_converter_off(&mut self, enable: bool) -> Result<(), Error<IFACE::Error>> {
+        let frc_conv_off = if enable {
+            if self.charge_option3().await?.cmp_en() != CmpEn::Enable {
+                return Err(Error::UnsafeTransition(UnsafeTransition::ComparatorDisabled));
+            }
+
+            FrcConvOff::Enable
+        } else {
+            FrcConvOff::Disable
+        };
+
+        let option1 = self.charge_option1().await?;
+        self.set_charge_option1(option1.with_frc_conv_off(frc_conv_off)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{AdcOption, AdcVbus, ChargeOption0, ChargeOption1, ChargeOption3, Register};
+    use crate::tests::{read, write};
+
+    fn vbus_adc() -> u16 {
+        AdcOption::from_bits(AdcOption::RESET)
+            .with_adc_en(AdcEn::Enable)
+            .with_adc_rate(AdcRate::Continuous)
+            .with_en_adc_vbus(EnAdcVbus::Enable)
+            .into_bits()
+    }
+
+    fn vbus(mv: i16) -> u16 {
+        AdcVbus::new().with_voltage(mv / 2).into_bits()
+    }
+
+    #[tokio::test]
+    async fn test_hiz() {
+        let option3 = ChargeOption3::from_bits(ChargeOption3::RESET).with_batfetoff_hiz(BatFetOffHiZ::On);
+        let hiz = option3.with_batfetoff_hiz(BatFetOffHiZ::Off).with_en_hiz(EnHiZ::Enable);
+        let otg = option3.with_en_otg(EnOtg::Enable);
+
+        let e = vec![
+            read::<ChargeOption3>(option3.into_bits()),
+            write::<ChargeOption3>(hiz.into_bits()),
+            read::<ChargeOption3>(hiz.into_bits()),
+            write::<ChargeOption3>(hiz.with_en_hiz(EnHiZ::Disable).into_bits()),
+            read::<ChargeOption3>(otg.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        bq.enter_hiz().await.unwrap();
+        bq.exit_hiz().await.unwrap();
+        assert_eq!(
+            bq.enter_hiz().await,
+            Err(Error::UnsafeTransition(UnsafeTransition::OtgActive))
+        );
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_battery_disconnect() {
+        let lwpwr = ChargeOption0::from_bits(ChargeOption0::RESET).with_en_lwpwr(LowPwrMode::Enable);
+        let normal = lwpwr.with_en_lwpwr(LowPwrMode::Disable);
+        let option3 = ChargeOption3::from_bits(ChargeOption3::RESET);
+
+        let e = vec![
+            read::<AdcOption>(AdcOption::RESET),
+            read::<AdcOption>(vbus_adc()),
+            read::<AdcVbus>(vbus(20000)),
+            read::<AdcOption>(vbus_adc()),
+            read::<AdcVbus>(vbus(0)),
+            read::<ChargeOption0>(normal.into_bits()),
+            read::<AdcOption>(vbus_adc()),
+            read::<AdcVbus>(vbus(0)),
+            read::<ChargeOption0>(lwpwr.into_bits()),
+            read::<ChargeOption3>(option3.into_bits()),
+            write::<ChargeOption3>(option3.with_batfet_enz(BatFetEnZ::ForceOff).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(
+            bq.battery_disconnect().await,
+            Err(Error::UnsafeTransition(UnsafeTransition::AdapterUnknown))
+        );
+        assert_eq!(
+            bq.battery_disconnect().await,
+            Err(Error::UnsafeTransition(UnsafeTransition::AdapterPresent))
+        );
+        assert_eq!(
+            bq.battery_disconnect().await,
+            Err(Error::UnsafeTransition(UnsafeTransition::NotLowPower))
+        );
+        bq.battery_disconnect().await.unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_prepare_ship_mode() {
+        let option0 = ChargeOption0::from_bits(ChargeOption0::RESET);
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET);
+
+        let e = vec![
+            read::<ChargeOption3>(ChargeOption3::RESET),
+            read::<AdcOption>(vbus_adc()),
+            read::<AdcVbus>(vbus(0)),
+            read::<ChargeOption0>(option0.into_bits()),
+            write::<ChargeOption0>(option0.with_chrg_inhibit(ChargeInhibit::Disable).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeOption1>(option1.with_en_ship_dchg(ShipDchg::Enable).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        bq.prepare_ship_mode().await.unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_comparator_converter_off() {
+        let option3 = ChargeOption3::from_bits(ChargeOption3::RESET);
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET);
+
+        let e = vec![
+            read::<ChargeOption3>(option3.with_cmp_en(CmpEn::Disable).into_bits()),
+            read::<ChargeOption3>(option3.with_cmp_en(CmpEn::Enable).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeOption1>(option1.with_frc_conv_off(FrcConvOff::Enable).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        assert_eq!(
+            bq.set_comparator_converter_off(true).await,
+            Err(Error::UnsafeTransition(UnsafeTransition::ComparatorDisabled))
+        );
+        bq.set_comparator_converter_off(true).await.unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
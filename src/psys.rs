@@ -0,0 +1,142 @@
+//! System power monitoring: converting the PSYS pin voltage to watts for
+//! the board's PSYS resistor.
+
+use crate::interface::RegisterInterface;
+use crate::registers::{AdcPsys, PsysConfig, PsysGain};
+use crate::{Bq2577x, Error};
+
+/// PSYS output resistor. The PSYS pin sources a current proportional to
+/// system power, so the pin voltage is that current times the resistor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Psys {
+    /// PSYS resistor to ground, in ohms.
+    pub resistor_ohms: u32,
+}
+
+impl Psys {
+    pub const fn new(resistor_ohms: u32) -> Self {
+        Self { resistor_ohms }
+    }
+
+    /// System power for a PSYS pin voltage, in milliwatts.
+    pub const fn milliwatts(&self, pin_mv: u16, gain: PsysGain) -> u32 {
+        let divisor = gain.nanoamps_per_watt() as u64 * self.resistor_ohms as u64;
+
+        if divisor == 0 {
+            return 0;
+        }
+
+        (pin_mv as u64 * 1_000_000_000 / divisor) as u32
+    }
+
+    /// Highest system power the ADC can read with `gain`, in
+    /// milliwatts.
+    pub const fn full_scale_mw(&self, gain: PsysGain) -> u32 {
+        self.milliwatts(AdcPsys::MAX_MV, gain)
+    }
+
+    /// Gain with the best resolution that still reads `full_scale_mw`,
+    /// or `None` if the resistor is too large for even the lowest gain.
+    pub const fn gain_for(&self, full_scale_mw: u32) -> Option<PsysGain> {
+        if full_scale_mw <= self.full_scale_mw(PsysGain::Gain1_00) {
+            Some(PsysGain::Gain1_00)
+        } else if full_scale_mw <= self.full_scale_mw(PsysGain::Gain0_25) {
+            Some(PsysGain::Gain0_25)
+        } else {
+            None
+        }
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Enables the PSYS circuit with the gain that best covers
+    /// `full_scale_mw`, measuring adapter and battery power, or adapter
+    /// power only if `include_battery` is false. Fails with
+    /// [`Error::OutOfRange`] if no gain reaches `full_scale_mw` with this
+    /// resistor.
+    pub async fn configure_psys(
+        &mut self,
+        psys: &Psys,
+        full_scale_mw: u32,
+        include_battery: bool,
+    ) -> Result<(), Error<IFACE::Error>> {
+        let gain = psys.gain_for(full_scale_mw).ok_or(Error::OutOfRange)?;
+
+        let config = if include_battery {
+            PsysConfig::PbusPbat
+        } else {
+            PsysConfig::Pbus
+        };
+
+        let option1 = self.charge_option1().await?;
+        self.set_charge_option1(option1.with_psys_ratio(gain).with_psys_config(config))
+            .await
+    }
+
+    /// Reads the system power from the PSYS ADC channel, in milliwatts,
+    /// or `None` if the PSYS circuit is off. The ADC must be converting
+    /// the PSYS channel, or the reading is stale.
+    pub async fn system_power_mw(&mut self, psys: &Psys) -> Result<Option<u32>, Error<IFACE::Error>> {
+        let option1 = self.charge_option1().await?;
+
+        if option1.psys_config() == PsysConfig::Off {
+            return Ok(None);
+        }
+
+        let pin_mv = self.adc_psys().await?.millivolts();
+
+        Ok(Some(psys.milliwatts(pin_mv, option1.psys_ratio())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargeOption1, Register};
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_psys_units() {
+        // 1µA/W into 10kΩ is 10mV/W
+        let psys = Psys::new(10_000);
+        assert_eq!(psys.milliwatts(650, PsysGain::Gain1_00), 65_000);
+        assert_eq!(psys.milliwatts(650, PsysGain::Gain0_25), 260_000);
+        assert_eq!(psys.full_scale_mw(PsysGain::Gain1_00), 819_100);
+
+        assert_eq!(psys.gain_for(500_000), Some(PsysGain::Gain1_00));
+        assert_eq!(psys.gain_for(1_000_000), Some(PsysGain::Gain0_25));
+        assert_eq!(psys.gain_for(4_000_000), None);
+    }
+
+    #[tokio::test]
+    async fn test_system_power() {
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET);
+        let enabled = option1
+            .with_psys_ratio(PsysGain::Gain0_25)
+            .with_psys_config(PsysConfig::PbusPbat);
+
+        let e = vec![
+            read::<ChargeOption1>(option1.into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeOption1>(enabled.into_bits()),
+            read::<ChargeOption1>(enabled.into_bits()),
+            read::<AdcPsys>(AdcPsys::new().with_power(1000).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let psys = Psys::new(20_000);
+
+        assert_eq!(bq.system_power_mw(&psys).await, Ok(None));
+        assert_eq!(bq.configure_psys(&psys, 2_000_000, true).await, Err(Error::OutOfRange));
+        bq.configure_psys(&psys, 1_000_000, true).await.unwrap();
+        assert_eq!(bq.system_power_mw(&psys).await, Ok(Some(200_000)));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -1,3 +1,128 @@
+/// Raw field encoding that the datasheet marks as reserved or invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidBits(pub u8);
+
+/// Register field holding a reserved or invalid encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidField {
+    /// Address of the register containing the field.
+    pub register: u8,
+
+    /// Name of the offending field.
+    pub field: &'static str,
+
+    /// Raw encoding found in the field.
+    pub value: u8,
+}
+
+/// Implements strict decoding for a register by validating each of its
+/// enumerated fields.
+macro_rules! impl_try_from_bits {
+    ($r:ty { $($field:ident: $ty:ty => ($offset:ident, $bits:ident)),* $(,)? }) => {
+        impl $r {
+            /// Like `from_bits`, but rejects reserved or invalid field
+            /// encodings.
+            pub const fn try_from_bits(value: u16) -> Result<Self, $crate::registers::InvalidField> {
+                $(
+                    let mask = u16::MAX >> (u16::BITS - Self::$bits as u32);
+                    let bits = ((value >> Self::$offset) & mask) as u8;
+                    if let Err($crate::registers::InvalidBits(bits)) = <$ty>::try_from_bits(bits) {
+                        return Err($crate::registers::InvalidField {
+                            register: Self::addr(),
+                            field: stringify!($field),
+                            value: bits,
+                        });
+                    }
+                )*
+
+                Ok(Self::from_bits(value))
+            }
+        }
+    };
+}
+
+/// Register access permissions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Access {
+    ReadOnly,
+    ReadWrite,
+}
+
+/// A BQ2577x register.
+pub trait Register: Copy {
+    /// Register address.
+    const ADDR: u8;
+
+    /// Power-on reset value.
+    const RESET: u16;
+
+    /// Access permissions.
+    const ACCESS: Access;
+
+    /// Decodes a raw register value.
+    fn from_bits(bits: u16) -> Self;
+
+    /// Decodes a raw register value, rejecting reserved or invalid
+    /// field encodings.
+    fn try_from_bits(bits: u16) -> Result<Self, InvalidField>;
+
+    /// Encodes the register into its raw value.
+    fn into_bits(self) -> u16;
+}
+
+/// Marker for registers that can be read.
+pub trait Readable: Register {}
+
+/// Marker for registers that can be written.
+///
+/// Read-only registers don't implement this trait, so writing them
+/// is rejected at compile time:
+///
+/// ```compile_fail
+/// # use bq2577x::{registers::AdcVbus, Bq2577x};
+/// # async fn f<IFACE: bq2577x::interface::RegisterInterface>(bq: &mut Bq2577x<IFACE>) {
+/// bq.write(AdcVbus::new()).await;
+/// # }
+/// ```
+pub trait Writable: Register {}
+
+/// Implements [`Register`] and the matching access markers.
+macro_rules! impl_register {
+    ($r:ty, $reset:expr, ReadOnly) => {
+        impl_register!(@register $r, $reset, ReadOnly);
+
+        impl $crate::registers::Readable for $r {}
+    };
+    ($r:ty, $reset:expr, ReadWrite) => {
+        impl_register!(@register $r, $reset, ReadWrite);
+
+        impl $crate::registers::Readable for $r {}
+        impl $crate::registers::Writable for $r {}
+    };
+    (@register $r:ty, $reset:expr, $access:ident) => {
+        impl $crate::registers::Register for $r {
+            const ADDR: u8 = <$r>::addr();
+            const RESET: u16 = $reset;
+            const ACCESS: $crate::registers::Access = $crate::registers::Access::$access;
+
+            fn from_bits(bits: u16) -> Self {
+                <$r>::from_bits(bits)
+            }
+
+            fn try_from_bits(bits: u16) -> Result<Self, $crate::registers::InvalidField> {
+                <$r>::try_from_bits(bits)
+            }
+
+            fn into_bits(self) -> u16 {
+                <$r>::into_bits(self)
+            }
+        }
+    };
+}
+
 mod adc_cmpin_tr;
 mod adc_ibat;
 mod adc_iin;
@@ -74,7 +199,8 @@ pub use virtual_control::*;
 pub use vmin_active_protection::*;
 pub use vsys_min::*;
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum WdTmrAdj {
     Disable,
@@ -88,7 +214,7 @@ impl WdTmrAdj {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             1 => Self::Seconds5,
@@ -96,9 +222,20 @@ impl WdTmrAdj {
             _ => Self::Seconds175,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Seconds5),
+            2 => Ok(Self::Seconds88),
+            3 => Ok(Self::Seconds175),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum WdRst {
     Normal,
@@ -110,15 +247,24 @@ impl WdRst {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Normal,
             _ => Self::Reset,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Reset),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AutoChg {
     Disable,
@@ -130,15 +276,24 @@ impl AutoChg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum RegReset {
     Idle = 0,
@@ -150,10 +305,454 @@ impl RegReset {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Idle,
             _ => Self::Reset,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Idle),
+            1 => Ok(Self::Reset),
+            _ => Err(InvalidBits(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_round_trip {
+        ($ty:ty, $bits:expr) => {
+            test_round_trip!($ty, $bits, [])
+        };
+        ($ty:ty, $bits:expr, [$($reserved:expr),*]) => {
+            let reserved: &[u8] = &[$($reserved),*];
+
+            for value in 0..=u8::MAX {
+                let valid = u16::from(value) < 1 << $bits && !reserved.contains(&value);
+
+                match <$ty>::try_from_bits(value) {
+                    Ok(field) => {
+                        assert!(valid, "{} accepted {:#x}", stringify!($ty), value);
+                        assert_eq!(<$ty>::from_bits(value), field);
+                        assert_eq!(field as u8, value);
+                    }
+                    Err(e) => {
+                        assert!(!valid, "{} rejected {:#x}", stringify!($ty), value);
+                        assert_eq!(e, InvalidBits(value));
+                    }
+                }
+            }
+        };
+    }
+
+    fn assert_derives<T: Clone + Copy + core::fmt::Debug + Eq + core::hash::Hash>() {}
+
+    #[cfg(feature = "defmt")]
+    fn assert_format<T: defmt::Format>() {}
+
+    macro_rules! test_derives {
+        ($($ty:ty),* $(,)?) => {
+            $(
+                assert_derives::<$ty>();
+                #[cfg(feature = "defmt")]
+                assert_format::<$ty>();
+            )*
+        };
+    }
+
+    macro_rules! test_defaults {
+        ($($r:ty),* $(,)?) => {
+            $(
+                assert_eq!(
+                    <$r>::default().into_bits(),
+                    <$r as Register>::RESET,
+                    "{} default differs from its reset value",
+                    stringify!($r)
+                );
+            )*
+        };
+    }
+
+    #[test]
+    fn test_defaults_of_registers() {
+        test_defaults!(
+            AdcCmpinTr,
+            AdcIbat,
+            AdcIin,
+            AdcOption,
+            AdcPsys,
+            AdcVbat,
+            AdcVbus,
+            AdcVsys,
+            AutoCharge,
+            AutotuneForce,
+            AutotuneRead,
+            ChargeCurrent,
+            ChargeOption0,
+            ChargeOption1,
+            ChargeOption2,
+            ChargeOption3,
+            ChargeOption4,
+            ChargeOption5,
+            ChargeProfile,
+            ChargeVoltage,
+            ChargerStatus0,
+            ChargerStatus1,
+            Device,
+            GateDrive,
+            GmAdjustForce,
+            IinDpm,
+            IinHost,
+            Manufacturer,
+            OtgCurrent,
+            OtgVoltage,
+            ProchotOption0,
+            ProchotOption1,
+            ProchotStatus,
+            VinDpm,
+            VirtualControl,
+            VminActiveProtection,
+            VsysMin,
+        );
+    }
+
+    #[test]
+    fn test_derives_of_types() {
+        test_derives!(
+            AdcCmpinTr,
+            AdcIbat,
+            AdcIin,
+            AdcOption,
+            AdcPsys,
+            AdcVbat,
+            AdcVbus,
+            AdcVsys,
+            AutoCharge,
+            AutotuneForce,
+            AutotuneRead,
+            ChargeCurrent,
+            ChargeOption0,
+            ChargeOption1,
+            ChargeOption2,
+            ChargeOption3,
+            ChargeOption4,
+            ChargeOption5,
+            ChargeProfile,
+            ChargeVoltage,
+            ChargerStatus0,
+            ChargerStatus1,
+            Device,
+            GateDrive,
+            GmAdjustForce,
+            IinDpm,
+            IinHost,
+            Manufacturer,
+            OtgCurrent,
+            OtgVoltage,
+            ProchotOption0,
+            ProchotOption1,
+            ProchotStatus,
+            VinDpm,
+            VirtualControl,
+            VminActiveProtection,
+            VsysMin,
+            AcocVth,
+            AcovProtection,
+            AdcAvg,
+            AdcAvgInit,
+            AdcEn,
+            AdcRate,
+            AdcSample,
+            AutoChg,
+            BatCocConfig,
+            BatDocVth,
+            BatFetEnZ,
+            BatFetOffHiZ,
+            BatOvpDchrg,
+            BatOvpExtend,
+            BatteryOVP,
+            BuckTransitionThreshold,
+            ChargeInhibit,
+            ChgTmr,
+            ChgTmrStat,
+            ChrgOkInt,
+            ChrgStat,
+            CmpDeg,
+            CmpEn,
+            CmpLatch,
+            CmpPol,
+            DetectVinDpm,
+            Dither,
+            DrvStat,
+            EnAcoc,
+            EnAdcCmpin,
+            EnAdcIbat,
+            EnAdcIin,
+            EnAdcPsys,
+            EnAdcVbat,
+            EnAdcVbus,
+            EnAdcVsys,
+            EnBatDoc,
+            EnChgTmr,
+            EnExtIlim,
+            EnHiZ,
+            EnIbat,
+            EnIchgIdchg,
+            EnIcoMode,
+            EnLwPwrCmp,
+            EnOtg,
+            EnOtgBigCap,
+            EnPkPwrIinDpm,
+            EnPkPwrVsys,
+            EnPortCtrl,
+            EnProchotExt,
+            EnTReg,
+            EnTmr2x,
+            EnVsysMinSoftSr,
+            FastRoleSwap,
+            Fault,
+            Follow,
+            ForceAutotune,
+            ForceGmAdjust,
+            ForceSingle,
+            ForceUpdate,
+            FrcConvOff,
+            Function,
+            Hiccup,
+            HighDutyBuck,
+            IadptAmplifierRatio,
+            IbatAmplifierRatio,
+            IcritDeg,
+            Idchg2Threshold,
+            IdchgDeg1,
+            IdchgDeg2,
+            IinDpmAutoDisable,
+            IinDpmEnable,
+            IlAvg,
+            Ilim2Vth,
+            IlimHiZ,
+            InomDeg,
+            LdoModeEnable,
+            Learn,
+            LowPwrMode,
+            LowerProchotVinDpm,
+            ModeStat,
+            OcpSw1x,
+            OcpSw2,
+            Otg,
+            OtgChrgOk,
+            OtgVapMode,
+            OutOfAudio,
+            PPAcok,
+            PPBatPres,
+            PPCmp,
+            PPIcrit,
+            PPIdchg1,
+            PPIdchg2,
+            PPInom,
+            PPThermal,
+            PPVbusVap,
+            PPVinDpm,
+            PPVsys,
+            PhAddDeg,
+            PhDropDeg,
+            PkPwrTmax,
+            PkPwrTovldDeg,
+            ProchotClear,
+            ProchotVinDpm,
+            ProchotWidth,
+            PsysConfig,
+            PsysGain,
+            PsysOtgIdchg,
+            Ptm,
+            PtmAutoExit,
+            PwmFrequency,
+            RegReset,
+            RegnExt,
+            RegnLwPwr,
+            RsnsRac,
+            RsnsRsr,
+            ShipDchg,
+            StatAdapterRemoval,
+            StatBatteryRemoval,
+            StatComp,
+            StatExitVap,
+            StatIcrit,
+            StatIdchg1,
+            StatIdchg2,
+            StatInom,
+            StatPkPwrOvld,
+            StatPkPwrRelax,
+            StatPtm,
+            StatThermal,
+            StatVapFail,
+            StatVbusVap,
+            StatVinDpm,
+            StatVsys,
+            SysOvpMax,
+            SystemUVP,
+            TRegDeg,
+            TRegStat,
+            Tshut,
+            VbusAcp,
+            VsysRegSlow,
+            VsysUvp,
+            WdRst,
+            WdTmrAdj,
+            InvalidBits,
+            InvalidField,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_of_fields() {
+        test_round_trip!(AcocVth, 1);
+        test_round_trip!(AcovProtection, 2);
+        test_round_trip!(AdcAvg, 1);
+        test_round_trip!(AdcAvgInit, 1);
+        test_round_trip!(AdcEn, 1);
+        test_round_trip!(AdcRate, 1);
+        test_round_trip!(AdcSample, 2, [3]);
+        test_round_trip!(AutoChg, 1);
+        test_round_trip!(BatCocConfig, 2);
+        test_round_trip!(BatDocVth, 1);
+        test_round_trip!(BatFetEnZ, 1);
+        test_round_trip!(BatFetOffHiZ, 1);
+        test_round_trip!(BatOvpDchrg, 1);
+        test_round_trip!(BatOvpExtend, 1);
+        test_round_trip!(BatteryOVP, 1);
+        test_round_trip!(BuckTransitionThreshold, 3);
+        test_round_trip!(ChargeInhibit, 1);
+        test_round_trip!(ChgTmr, 2);
+        test_round_trip!(ChgTmrStat, 1);
+        test_round_trip!(ChrgOkInt, 1);
+        test_round_trip!(ChrgStat, 3, [5, 6]);
+        test_round_trip!(CmpDeg, 2);
+        test_round_trip!(CmpEn, 1);
+        test_round_trip!(CmpLatch, 1);
+        test_round_trip!(CmpPol, 1);
+        test_round_trip!(DetectVinDpm, 1);
+        test_round_trip!(Dither, 2);
+        test_round_trip!(DrvStat, 3);
+        test_round_trip!(EnAcoc, 1);
+        test_round_trip!(EnAdcCmpin, 1);
+        test_round_trip!(EnAdcIbat, 1);
+        test_round_trip!(EnAdcIin, 1);
+        test_round_trip!(EnAdcPsys, 1);
+        test_round_trip!(EnAdcVbat, 1);
+        test_round_trip!(EnAdcVbus, 1);
+        test_round_trip!(EnAdcVsys, 1);
+        test_round_trip!(EnBatDoc, 1);
+        test_round_trip!(EnChgTmr, 1);
+        test_round_trip!(EnExtIlim, 1);
+        test_round_trip!(EnHiZ, 1);
+        test_round_trip!(EnIbat, 1);
+        test_round_trip!(EnIchgIdchg, 1);
+        test_round_trip!(EnIcoMode, 1);
+        test_round_trip!(EnLwPwrCmp, 1);
+        test_round_trip!(EnOtg, 1);
+        test_round_trip!(EnOtgBigCap, 1);
+        test_round_trip!(EnPkPwrIinDpm, 1);
+        test_round_trip!(EnPkPwrVsys, 1);
+        test_round_trip!(EnPortCtrl, 1);
+        test_round_trip!(EnProchotExt, 1);
+        test_round_trip!(EnTReg, 1);
+        test_round_trip!(EnTmr2x, 1);
+        test_round_trip!(EnVsysMinSoftSr, 2);
+        test_round_trip!(FastRoleSwap, 1);
+        test_round_trip!(Fault, 1);
+        test_round_trip!(Follow, 1);
+        test_round_trip!(ForceAutotune, 1);
+        test_round_trip!(ForceGmAdjust, 1);
+        test_round_trip!(ForceSingle, 1);
+        test_round_trip!(ForceUpdate, 1);
+        test_round_trip!(FrcConvOff, 1);
+        test_round_trip!(Function, 1);
+        test_round_trip!(Hiccup, 1);
+        test_round_trip!(HighDutyBuck, 1);
+        test_round_trip!(IadptAmplifierRatio, 1);
+        test_round_trip!(IbatAmplifierRatio, 1);
+        test_round_trip!(IcritDeg, 2);
+        test_round_trip!(Idchg2Threshold, 3);
+        test_round_trip!(IdchgDeg1, 2);
+        test_round_trip!(IdchgDeg2, 2);
+        test_round_trip!(IinDpmAutoDisable, 1);
+        test_round_trip!(IinDpmEnable, 1);
+        test_round_trip!(IlAvg, 2);
+        test_round_trip!(Ilim2Vth, 5, [0, 31]);
+        test_round_trip!(IlimHiZ, 1);
+        test_round_trip!(InomDeg, 1);
+        test_round_trip!(LdoModeEnable, 1);
+        test_round_trip!(Learn, 1);
+        test_round_trip!(LowPwrMode, 1);
+        test_round_trip!(LowerProchotVinDpm, 1);
+        test_round_trip!(ModeStat, 3);
+        test_round_trip!(OcpSw1x, 1);
+        test_round_trip!(OcpSw2, 1);
+        test_round_trip!(Otg, 1);
+        test_round_trip!(OtgChrgOk, 1);
+        test_round_trip!(OtgVapMode, 1);
+        test_round_trip!(OutOfAudio, 1);
+        test_round_trip!(PPAcok, 1);
+        test_round_trip!(PPBatPres, 1);
+        test_round_trip!(PPCmp, 1);
+        test_round_trip!(PPIcrit, 1);
+        test_round_trip!(PPIdchg1, 1);
+        test_round_trip!(PPIdchg2, 1);
+        test_round_trip!(PPInom, 1);
+        test_round_trip!(PPThermal, 1);
+        test_round_trip!(PPVbusVap, 1);
+        test_round_trip!(PPVinDpm, 1);
+        test_round_trip!(PPVsys, 1);
+        test_round_trip!(PhAddDeg, 2);
+        test_round_trip!(PhDropDeg, 2);
+        test_round_trip!(PkPwrTmax, 2);
+        test_round_trip!(PkPwrTovldDeg, 2);
+        test_round_trip!(ProchotClear, 1);
+        test_round_trip!(ProchotVinDpm, 1);
+        test_round_trip!(ProchotWidth, 2);
+        test_round_trip!(PsysConfig, 2, [2]);
+        test_round_trip!(PsysGain, 1);
+        test_round_trip!(PsysOtgIdchg, 1);
+        test_round_trip!(Ptm, 1);
+        test_round_trip!(PtmAutoExit, 1);
+        test_round_trip!(PwmFrequency, 1);
+        test_round_trip!(RegReset, 1);
+        test_round_trip!(RegnExt, 1);
+        test_round_trip!(RegnLwPwr, 1);
+        test_round_trip!(RsnsRac, 1);
+        test_round_trip!(RsnsRsr, 1);
+        test_round_trip!(ShipDchg, 1);
+        test_round_trip!(StatAdapterRemoval, 1);
+        test_round_trip!(StatBatteryRemoval, 1);
+        test_round_trip!(StatComp, 1);
+        test_round_trip!(StatExitVap, 1);
+        test_round_trip!(StatIcrit, 1);
+        test_round_trip!(StatIdchg1, 1);
+        test_round_trip!(StatIdchg2, 1);
+        test_round_trip!(StatInom, 1);
+        test_round_trip!(StatPkPwrOvld, 1);
+        test_round_trip!(StatPkPwrRelax, 1);
+        test_round_trip!(StatPtm, 1);
+        test_round_trip!(StatThermal, 1);
+        test_round_trip!(StatVapFail, 1);
+        test_round_trip!(StatVbusVap, 1);
+        test_round_trip!(StatVinDpm, 1);
+        test_round_trip!(StatVsys, 1);
+        test_round_trip!(SysOvpMax, 1);
+        test_round_trip!(SystemUVP, 1);
+        test_round_trip!(TRegDeg, 1);
+        test_round_trip!(TRegStat, 1);
+        test_round_trip!(Tshut, 1);
+        test_round_trip!(VbusAcp, 1);
+        test_round_trip!(VsysRegSlow, 1);
+        test_round_trip!(VsysUvp, 3);
+        test_round_trip!(WdRst, 1);
+        test_round_trip!(WdTmrAdj, 2);
+    }
 }
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// ADC CMPIN TR Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcCmpinTr {
     /// CMPIN_TR pin voltage ADC reading.
     ///
@@ -22,3 +22,7 @@ impl AdcCmpinTr {
         0x29
     }
 }
+
+impl_try_from_bits!(AdcCmpinTr {});
+
+impl_register!(AdcCmpinTr, 0x0000, ReadOnly);
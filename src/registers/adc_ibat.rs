@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::RsnsRsr;
+
 /// ADC Ibat Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcIbat {
     /// IBAT ADC reading with 5mΩ sense resistor: Note the charger
     /// only measures discharging current (negative voltage) under
@@ -25,4 +27,14 @@ impl AdcIbat {
     pub(crate) const fn addr() -> u8 {
         0x24
     }
+
+    /// Battery current in milliamps for the given RSR sense resistor;
+    /// positive when charging, negative when discharging.
+    pub const fn milliamps(&self, rsr: RsnsRsr) -> i32 {
+        self.current() as i32 * 5 / rsr.milliohms() as i32
+    }
 }
+
+impl_try_from_bits!(AdcIbat {});
+
+impl_register!(AdcIbat, 0x0000, ReadOnly);
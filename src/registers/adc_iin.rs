@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::RsnsRac;
+
 /// ADC Iin Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcIin {
     /// IIN ADC reading with 10mÎ© sense resistor: current flowing from
     /// the adapter to the co nverter (like in forward mode) is
@@ -24,4 +26,14 @@ impl AdcIin {
     pub(crate) const fn addr() -> u8 {
         0x25
     }
+
+    /// Input current in milliamps for the given RAC sense resistor;
+    /// positive from the adapter, negative towards VBUS in OTG mode.
+    pub const fn milliamps(&self, rac: RsnsRac) -> i32 {
+        self.current() as i32 * 5 / rac.milliohms() as i32
+    }
 }
+
+impl_try_from_bits!(AdcIin {});
+
+impl_register!(AdcIin, 0x0000, ReadOnly);
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// ADC Option Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcOption {
     /// Enable SRN pin Voltage ADC Channel.
     ///
@@ -133,7 +135,25 @@ impl AdcOption {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(AdcOption {
+    en_adc_vbat: EnAdcVbat => (EN_ADC_VBAT_OFFSET, EN_ADC_VBAT_BITS),
+    en_adc_vsys: EnAdcVsys => (EN_ADC_VSYS_OFFSET, EN_ADC_VSYS_BITS),
+    en_adc_ibat: EnAdcIbat => (EN_ADC_IBAT_OFFSET, EN_ADC_IBAT_BITS),
+    en_adc_iin: EnAdcIin => (EN_ADC_IIN_OFFSET, EN_ADC_IIN_BITS),
+    en_adc_psys: EnAdcPsys => (EN_ADC_PSYS_OFFSET, EN_ADC_PSYS_BITS),
+    en_adc_vbus: EnAdcVbus => (EN_ADC_VBUS_OFFSET, EN_ADC_VBUS_BITS),
+    en_adc_cmpin: EnAdcCmpin => (EN_ADC_CMPIN_OFFSET, EN_ADC_CMPIN_BITS),
+    adc_avg_init: AdcAvgInit => (ADC_AVG_INIT_OFFSET, ADC_AVG_INIT_BITS),
+    adc_avg: AdcAvg => (ADC_AVG_OFFSET, ADC_AVG_BITS),
+    adc_sample: AdcSample => (ADC_SAMPLE_OFFSET, ADC_SAMPLE_BITS),
+    adc_en: AdcEn => (ADC_EN_OFFSET, ADC_EN_BITS),
+    adc_rate: AdcRate => (ADC_RATE_OFFSET, ADC_RATE_BITS),
+});
+
+impl_register!(AdcOption, 0x9000, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcVbat {
     Disable = 0,
@@ -145,15 +165,24 @@ impl EnAdcVbat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcVsys {
     Disable = 0,
@@ -165,15 +194,24 @@ impl EnAdcVsys {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcIbat {
     Disable = 0,
@@ -185,15 +223,24 @@ impl EnAdcIbat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcIin {
     Disable = 0,
@@ -205,15 +252,24 @@ impl EnAdcIin {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcPsys {
     Disable = 0,
@@ -225,15 +281,24 @@ impl EnAdcPsys {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcVbus {
     Disable = 0,
@@ -245,15 +310,24 @@ impl EnAdcVbus {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAdcCmpin {
     Disable = 0,
@@ -265,15 +339,24 @@ impl EnAdcCmpin {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AdcAvgInit {
     UseExisting = 0,
@@ -285,15 +368,24 @@ impl AdcAvgInit {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::UseExisting,
             _ => Self::StartNewAverage,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::UseExisting),
+            1 => Ok(Self::StartNewAverage),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AdcAvg {
     SingleValue = 0,
@@ -305,15 +397,24 @@ impl AdcAvg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::SingleValue,
             _ => Self::RunningAvg,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::SingleValue),
+            1 => Ok(Self::RunningAvg),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AdcSample {
     Bits15 = 0,
@@ -326,16 +427,26 @@ impl AdcSample {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Bits15,
             1 => Self::Bits14,
             _ => Self::Bits13,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Bits15),
+            1 => Ok(Self::Bits14),
+            2 => Ok(Self::Bits13),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AdcEn {
     Disable = 0,
@@ -347,15 +458,24 @@ impl AdcEn {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AdcRate {
     Continuous = 0,
@@ -367,10 +487,18 @@ impl AdcRate {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Continuous,
             _ => Self::OneShot,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Continuous),
+            1 => Ok(Self::OneShot),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// ADC Psys Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcPsys {
     /// System Power PSYS ADC reading.
     ///
@@ -18,7 +18,19 @@ pub struct AdcPsys {
 }
 
 impl AdcPsys {
+    /// Reading at which the ADC clamps, in millivolts.
+    pub const MAX_MV: u16 = 0x1fff;
+
     pub(crate) const fn addr() -> u8 {
         0x28
     }
+
+    /// PSYS pin voltage in millivolts.
+    pub const fn millivolts(&self) -> u16 {
+        self.power()
+    }
 }
+
+impl_try_from_bits!(AdcPsys {});
+
+impl_register!(AdcPsys, 0x0000, ReadOnly);
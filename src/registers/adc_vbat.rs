@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// ADC Vbat Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcVbat {
     /// VBAT ADC reading: POR: 0mV (0h)
     ///
@@ -21,4 +21,13 @@ impl AdcVbat {
     pub(crate) const fn addr() -> u8 {
         0x27
     }
+
+    /// Battery voltage in millivolts.
+    pub const fn millivolts(&self) -> i32 {
+        self.voltage() as i32
+    }
 }
+
+impl_try_from_bits!(AdcVbat {});
+
+impl_register!(AdcVbat, 0x0000, ReadOnly);
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// ADC Vbus Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcVbus {
     /// VBUS ADC reading:
     ///
@@ -27,4 +27,13 @@ impl AdcVbus {
     pub(crate) const fn addr() -> u8 {
         0x23
     }
+
+    /// Input voltage in millivolts.
+    pub const fn millivolts(&self) -> i32 {
+        self.voltage() as i32 * 2
+    }
 }
+
+impl_try_from_bits!(AdcVbus {});
+
+impl_register!(AdcVbus, 0x0000, ReadOnly);
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// ADC Vsys Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AdcVsys {
     /// VSYS ADC reading.
     ///
@@ -23,4 +23,13 @@ impl AdcVsys {
     pub(crate) const fn addr() -> u8 {
         0x26
     }
+
+    /// System voltage in millivolts.
+    pub const fn millivolts(&self) -> i32 {
+        self.voltage() as i32 * 2
+    }
 }
+
+impl_try_from_bits!(AdcVsys {});
+
+impl_register!(AdcVsys, 0x0000, ReadOnly);
@@ -1,10 +1,10 @@
 use bitfield_struct::bitfield;
 
-use super::AutoChg;
+use super::{AutoChg, InvalidBits};
 
 /// Auto Charge Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AutoCharge {
     /// ACOV protection threshold adjustment.
     ///
@@ -15,7 +15,7 @@ pub struct AutoCharge {
     /// 10b = 33V(28V EPR)
     ///
     /// 11b = 41V(36V EPR)
-    #[bits(2, default = AcovProtection::Threshold25V)]
+    #[bits(2, default = AcovProtection::Threshold33V)]
     pub acov_adj: AcovProtection,
 
     /// Adjust TREG thermal deglitch time to trigger prochot profile
@@ -142,7 +142,23 @@ impl AutoCharge {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(AutoCharge {
+    acov_adj: AcovProtection => (ACOV_ADJ_OFFSET, ACOV_ADJ_BITS),
+    thermal_deg: TRegDeg => (THERMAL_DEG_OFFSET, THERMAL_DEG_BITS),
+    stat_thermal: StatThermal => (STAT_THERMAL_OFFSET, STAT_THERMAL_BITS),
+    pp_thermal: PPThermal => (PP_THERMAL_OFFSET, PP_THERMAL_BITS),
+    en_treg: EnTReg => (EN_TREG_OFFSET, EN_TREG_BITS),
+    en_chg_tmr: EnChgTmr => (EN_CHG_TMR_OFFSET, EN_CHG_TMR_BITS),
+    en_tmr2x: EnTmr2x => (EN_TMR2X_OFFSET, EN_TMR2X_BITS),
+    chg_tmr: ChgTmr => (CHG_TMR_OFFSET, CHG_TMR_BITS),
+    chrg_ok_int: ChrgOkInt => (CHRG_OK_INT_OFFSET, CHRG_OK_INT_BITS),
+    en_auto_chg: AutoChg => (EN_AUTO_CHG_OFFSET, EN_AUTO_CHG_BITS),
+});
+
+impl_register!(AutoCharge, 0x01c2, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AcovProtection {
     Threshold20V,
@@ -156,7 +172,7 @@ impl AcovProtection {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Threshold20V,
             1 => Self::Threshold25V,
@@ -164,9 +180,20 @@ impl AcovProtection {
             _ => Self::Threshold41V,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Threshold20V),
+            1 => Ok(Self::Threshold25V),
+            2 => Ok(Self::Threshold33V),
+            3 => Ok(Self::Threshold41V),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum TRegDeg {
     Time965ms,
@@ -178,15 +205,24 @@ impl TRegDeg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time965ms,
             _ => Self::Time121ms,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time965ms),
+            1 => Ok(Self::Time121ms),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatThermal {
     NotTriggered,
@@ -198,15 +234,24 @@ impl StatThermal {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPThermal {
     Disable,
@@ -218,15 +263,24 @@ impl PPThermal {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnTReg {
     Disable,
@@ -238,15 +292,24 @@ impl EnTReg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnChgTmr {
     Disable,
@@ -258,15 +321,24 @@ impl EnChgTmr {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnTmr2x {
     Normal,
@@ -278,15 +350,24 @@ impl EnTmr2x {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Normal,
             _ => Self::Slow,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Slow),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ChgTmr {
     Timer5h,
@@ -300,7 +381,7 @@ impl ChgTmr {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Timer5h,
             1 => Self::Timer8h,
@@ -308,9 +389,20 @@ impl ChgTmr {
             _ => Self::Timer24h,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Timer5h),
+            1 => Ok(Self::Timer8h),
+            2 => Ok(Self::Timer12h),
+            3 => Ok(Self::Timer24h),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ChrgOkInt {
     Disable,
@@ -322,10 +414,18 @@ impl ChrgOkInt {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
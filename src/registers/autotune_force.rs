@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Autotune Force Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AutotuneForce {
     /// Force value for phase B inductor time constant L(uH)/DCR(mΩ).
     ///
@@ -26,3 +26,7 @@ impl AutotuneForce {
         0x61
     }
 }
+
+impl_try_from_bits!(AutotuneForce {});
+
+impl_register!(AutotuneForce, 0xa8a8, ReadWrite);
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Autotune Read Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct AutotuneRead {
     /// Phase B inductor time constant L(uH)/DCR(mΩ) value.
     ///
@@ -24,3 +24,7 @@ impl AutotuneRead {
         0x60
     }
 }
+
+impl_try_from_bits!(AutotuneRead {});
+
+impl_register!(AutotuneRead, 0x0000, ReadWrite);
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::RsnsRsr;
+
 /// Charge Current Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeCurrent {
     #[bits(3, default = 0)]
     reserved2_0: u8,
@@ -36,7 +38,34 @@ pub struct ChargeCurrent {
 }
 
 impl ChargeCurrent {
+    const STEP_MA: u16 = 8;
+    const MAX: u16 = 0x7f8;
+    const MAX_2MOHM: u16 = 0x5dc;
+
     pub(crate) const fn addr() -> u8 {
         0x14
     }
+
+    /// Charge current in milliamps for the given RSR sense resistor.
+    pub const fn milliamps(&self, rsr: RsnsRsr) -> u16 {
+        self.current() * rsr.scale(Self::STEP_MA)
+    }
+
+    /// Sets the charge current in milliamps for the given RSR sense
+    /// resistor, clamped to the register range like the charger itself
+    /// does.
+    pub const fn with_milliamps(self, ma: u16, rsr: RsnsRsr) -> Self {
+        let current = ma / rsr.scale(Self::STEP_MA);
+
+        let max = match rsr {
+            RsnsRsr::MilliOhms5 => Self::MAX,
+            RsnsRsr::MilliOhms2 => Self::MAX_2MOHM,
+        };
+
+        self.with_current(if current > max { max } else { current })
+    }
 }
+
+impl_try_from_bits!(ChargeCurrent {});
+
+impl_register!(ChargeCurrent, 0x0000, ReadWrite);
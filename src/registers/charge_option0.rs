@@ -1,10 +1,10 @@
 use bitfield_struct::bitfield;
 
-use super::WdTmrAdj;
+use super::{InvalidBits, WdTmrAdj};
 
 /// Charge Option 0 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeOption0 {
     /// Charge Inhibit
     ///
@@ -182,7 +182,28 @@ impl ChargeOption0 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargeOption0 {
+    chrg_inhibit: ChargeInhibit => (CHRG_INHIBIT_OFFSET, CHRG_INHIBIT_BITS),
+    iin_dpm_enable: IinDpmEnable => (IIN_DPM_ENABLE_OFFSET, IIN_DPM_ENABLE_BITS),
+    en_ldo: LdoModeEnable => (EN_LDO_OFFSET, EN_LDO_BITS),
+    ibat_gain: IbatAmplifierRatio => (IBAT_GAIN_OFFSET, IBAT_GAIN_BITS),
+    iadpt_gain: IadptAmplifierRatio => (IADPT_GAIN_OFFSET, IADPT_GAIN_BITS),
+    en_learn: Learn => (EN_LEARN_OFFSET, EN_LEARN_BITS),
+    vsys_uvp_enz: SystemUVP => (VSYS_UVP_ENZ_OFFSET, VSYS_UVP_ENZ_BITS),
+    en_cmp_latch: CmpLatch => (EN_CMP_LATCH_OFFSET, EN_CMP_LATCH_BITS),
+    en_batovp: BatteryOVP => (EN_BATOVP_OFFSET, EN_BATOVP_BITS),
+    pwm_freq: PwmFrequency => (PWM_FREQ_OFFSET, PWM_FREQ_BITS),
+    en_ooa: OutOfAudio => (EN_OOA_OFFSET, EN_OOA_BITS),
+    otg_on_chrgok: OtgChrgOk => (OTG_ON_CHRGOK_OFFSET, OTG_ON_CHRGOK_BITS),
+    iin_dpm_auto_disable: IinDpmAutoDisable => (IIN_DPM_AUTO_DISABLE_OFFSET, IIN_DPM_AUTO_DISABLE_BITS),
+    wdtmr_adj: WdTmrAdj => (WDTMR_ADJ_OFFSET, WDTMR_ADJ_BITS),
+    en_lwpwr: LowPwrMode => (EN_LWPWR_OFFSET, EN_LWPWR_BITS),
+});
+
+impl_register!(ChargeOption0, 0xe70e, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ChargeInhibit {
     Enable = 0,
@@ -194,15 +215,24 @@ impl ChargeInhibit {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             1 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Enable),
+            1 => Ok(Self::Disable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IinDpmEnable {
     Disable = 0,
@@ -214,15 +244,24 @@ impl IinDpmEnable {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum LdoModeEnable {
     Disable = 0,
@@ -234,15 +273,24 @@ impl LdoModeEnable {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IbatAmplifierRatio {
     Times8 = 0,
@@ -254,15 +302,24 @@ impl IbatAmplifierRatio {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Times8,
             _ => Self::Times64,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Times8),
+            1 => Ok(Self::Times64),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IadptAmplifierRatio {
     Times20 = 0,
@@ -274,15 +331,24 @@ impl IadptAmplifierRatio {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Times20,
             _ => Self::Times40,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Times20),
+            1 => Ok(Self::Times40),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Learn {
     Disable = 0,
@@ -294,15 +360,24 @@ impl Learn {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum SystemUVP {
     Enable = 0,
@@ -314,15 +389,24 @@ impl SystemUVP {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
-            0 => Self::Disable,
-            _ => Self::Enable,
+            0 => Self::Enable,
+            _ => Self::Disable,
+        }
+    }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Enable),
+            1 => Ok(Self::Disable),
+            _ => Err(InvalidBits(value)),
         }
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CmpLatch {
     NoLatch = 0,
@@ -334,15 +418,24 @@ impl CmpLatch {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NoLatch,
             _ => Self::Latch,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NoLatch),
+            1 => Ok(Self::Latch),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatteryOVP {
     Disable = 0,
@@ -354,15 +447,24 @@ impl BatteryOVP {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PwmFrequency {
     KHz800 = 0,
@@ -374,15 +476,24 @@ impl PwmFrequency {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::KHz800,
             _ => Self::KHz600,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::KHz800),
+            1 => Ok(Self::KHz600),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OutOfAudio {
     NoLimit = 0,
@@ -394,15 +505,24 @@ impl OutOfAudio {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NoLimit,
             _ => Self::Limit,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NoLimit),
+            1 => Ok(Self::Limit),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OtgChrgOk {
     Disable = 0,
@@ -414,15 +534,24 @@ impl OtgChrgOk {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IinDpmAutoDisable {
     Disable = 0,
@@ -434,15 +563,24 @@ impl IinDpmAutoDisable {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum LowPwrMode {
     Disable,
@@ -454,10 +592,18 @@ impl LowPwrMode {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Charge Option 1 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeOption1 {
     /// SC_VBUSACP protection enable register bit.
     ///
@@ -191,7 +193,27 @@ impl ChargeOption1 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargeOption1 {
+    en_sc_vbusacp: VbusAcp => (EN_SC_VBUSACP_OFFSET, EN_SC_VBUSACP_BITS),
+    en_ship_dchg: ShipDchg => (EN_SHIP_DCHG_OFFSET, EN_SHIP_DCHG_BITS),
+    en_ptm: Ptm => (EN_PTM_OFFSET, EN_PTM_BITS),
+    frc_conv_off: FrcConvOff => (FRC_CONV_OFF_OFFSET, FRC_CONV_OFF_BITS),
+    cmp_deg: CmpDeg => (CMP_DEG_OFFSET, CMP_DEG_BITS),
+    cmp_pol: CmpPol => (CMP_POL_OFFSET, CMP_POL_BITS),
+    sysovp_max: SysOvpMax => (SYSOVP_MAX_OFFSET, SYSOVP_MAX_BITS),
+    en_otg_big_cap: EnOtgBigCap => (EN_OTG_BIG_CAP_OFFSET, EN_OTG_BIG_CAP_BITS),
+    psys_ratio: PsysGain => (PSYS_RATIO_OFFSET, PSYS_RATIO_BITS),
+    rsns_rsr: RsnsRsr => (RSNS_RSR_OFFSET, RSNS_RSR_BITS),
+    rsns_rac: RsnsRac => (RSNS_RAC_OFFSET, RSNS_RAC_BITS),
+    psys_config: PsysConfig => (PSYS_CONFIG_OFFSET, PSYS_CONFIG_BITS),
+    en_lwpwr_cmp: EnLwPwrCmp => (EN_LWPWR_CMP_OFFSET, EN_LWPWR_CMP_BITS),
+    en_ibat: EnIbat => (EN_IBAT_OFFSET, EN_IBAT_BITS),
+});
+
+impl_register!(ChargeOption1, 0x3201, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum VbusAcp {
     Disable = 0,
@@ -203,15 +225,24 @@ impl VbusAcp {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ShipDchg {
     Disable = 0,
@@ -223,15 +254,24 @@ impl ShipDchg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Ptm {
     Disable = 0,
@@ -243,15 +283,24 @@ impl Ptm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum FrcConvOff {
     Disable = 0,
@@ -263,15 +312,24 @@ impl FrcConvOff {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CmpDeg {
     Time1us = 0,
@@ -285,7 +343,7 @@ impl CmpDeg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time1us,
             1 => Self::Time20500us,
@@ -293,9 +351,20 @@ impl CmpDeg {
             _ => Self::Time5340000us,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time1us),
+            1 => Ok(Self::Time20500us),
+            2 => Ok(Self::Time20850us),
+            3 => Ok(Self::Time5340000us),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CmpPol {
     ActiveLow = 0,
@@ -307,15 +376,24 @@ impl CmpPol {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::ActiveLow,
             _ => Self::ActiveHigh,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::ActiveLow),
+            1 => Ok(Self::ActiveHigh),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum SysOvpMax {
     Disable = 0,
@@ -327,15 +405,24 @@ impl SysOvpMax {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnOtgBigCap {
     Disable = 0,
@@ -347,15 +434,24 @@ impl EnOtgBigCap {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PsysGain {
     Gain0_25 = 0,
@@ -363,19 +459,36 @@ pub enum PsysGain {
 }
 
 impl PsysGain {
+    /// PSYS output current per watt, in nanoamps.
+    pub const fn nanoamps_per_watt(self) -> u32 {
+        match self {
+            Self::Gain0_25 => 250,
+            Self::Gain1_00 => 1000,
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Gain0_25,
             _ => Self::Gain1_00,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Gain0_25),
+            1 => Ok(Self::Gain1_00),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum RsnsRsr {
     MilliOhms5 = 0,
@@ -383,19 +496,42 @@ pub enum RsnsRsr {
 }
 
 impl RsnsRsr {
+    /// Sense resistor value in milliohms.
+    pub const fn milliohms(self) -> u16 {
+        match self {
+            Self::MilliOhms5 => 5,
+            Self::MilliOhms2 => 2,
+        }
+    }
+
+    /// Scales a current step specified for a 5mΩ sense resistor to
+    /// this sense resistor.
+    pub(crate) const fn scale(self, ma: u16) -> u16 {
+        ma * 5 / self.milliohms()
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::MilliOhms5,
             _ => Self::MilliOhms2,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::MilliOhms5),
+            1 => Ok(Self::MilliOhms2),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum RsnsRac {
     MilliOhms10 = 0,
@@ -403,19 +539,42 @@ pub enum RsnsRac {
 }
 
 impl RsnsRac {
+    /// Sense resistor value in milliohms.
+    pub const fn milliohms(self) -> u16 {
+        match self {
+            Self::MilliOhms10 => 10,
+            Self::MilliOhms5 => 5,
+        }
+    }
+
+    /// Scales a current step specified for a 10mΩ sense resistor to
+    /// this sense resistor.
+    pub(crate) const fn scale(self, ma: u16) -> u16 {
+        ma * 10 / self.milliohms()
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::MilliOhms10,
             _ => Self::MilliOhms5,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::MilliOhms10),
+            1 => Ok(Self::MilliOhms5),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PsysConfig {
     PbusPbat = 0,
@@ -428,16 +587,26 @@ impl PsysConfig {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::PbusPbat,
             1 => Self::Pbus,
             _ => Self::Off,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::PbusPbat),
+            1 => Ok(Self::Pbus),
+            3 => Ok(Self::Off),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnLwPwrCmp {
     Disable = 0,
@@ -449,15 +618,24 @@ impl EnLwPwrCmp {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnIbat {
     Disable = 0,
@@ -469,10 +647,18 @@ impl EnIbat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
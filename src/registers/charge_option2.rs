@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Charge Option 2 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeOption2 {
     /// Set battery discharge overcurrent threshold as percentage of
     /// PROCHOT battery discharge current limit.
@@ -45,7 +47,7 @@ pub struct ChargeOption2 {
     /// 0b = Disable
     ///
     /// 1b = Enable
-    #[bits(1, default = EnAcoc::Enable)]
+    #[bits(1, default = EnAcoc::Disable)]
     pub en_acoc: EnAcoc,
 
     /// Over current protection threshold by sensing RAC resistor
@@ -159,7 +161,27 @@ impl ChargeOption2 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargeOption2 {
+    batdoc_vth: BatDocVth => (BATDOC_VTH_OFFSET, BATDOC_VTH_BITS),
+    en_batdoc: EnBatDoc => (EN_BATDOC_OFFSET, EN_BATDOC_BITS),
+    acoc_vth: AcocVth => (ACOC_VTH_OFFSET, ACOC_VTH_BITS),
+    en_acoc: EnAcoc => (EN_ACOC_OFFSET, EN_ACOC_BITS),
+    ocp_sw1x_high_range: OcpSw1x => (OCP_SW1X_HIGH_RANGE_OFFSET, OCP_SW1X_HIGH_RANGE_BITS),
+    ocp_sw2_high_range: OcpSw2 => (OCP_SW2_HIGH_RANGE_OFFSET, OCP_SW2_HIGH_RANGE_BITS),
+    en_ichg_idchg: EnIchgIdchg => (EN_ICHG_IDCHG_OFFSET, EN_ICHG_IDCHG_BITS),
+    en_extilim: EnExtIlim => (EN_EXTILIM_OFFSET, EN_EXTILIM_BITS),
+    pkpwr_tmax: PkPwrTmax => (PKPWR_TMAX_OFFSET, PKPWR_TMAX_BITS),
+    stat_pkpwr_relax: StatPkPwrRelax => (STAT_PKPWR_RELAX_OFFSET, STAT_PKPWR_RELAX_BITS),
+    stat_pkpwr_ovld: StatPkPwrOvld => (STAT_PKPWR_OVLD_OFFSET, STAT_PKPWR_OVLD_BITS),
+    en_pkpwr_vsys: EnPkPwrVsys => (EN_PKPWR_VSYS_OFFSET, EN_PKPWR_VSYS_BITS),
+    en_pkpwr_iin_dpm: EnPkPwrIinDpm => (EN_PKPWR_IIN_DPM_OFFSET, EN_PKPWR_IIN_DPM_BITS),
+    pkpwr_tovld_deg: PkPwrTovldDeg => (PKPWR_TOVLD_DEG_OFFSET, PKPWR_TOVLD_DEG_BITS),
+});
+
+impl_register!(ChargeOption2, 0x00b7, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatDocVth {
     Threshold2 = 0,
@@ -171,15 +193,24 @@ impl BatDocVth {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Threshold2,
             _ => Self::Threshold3,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Threshold2),
+            1 => Ok(Self::Threshold3),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnBatDoc {
     Disable = 0,
@@ -191,15 +222,24 @@ impl EnBatDoc {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum AcocVth {
     Threshold1_33 = 0,
@@ -211,15 +251,24 @@ impl AcocVth {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Threshold1_33,
             _ => Self::Threshold2,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Threshold1_33),
+            1 => Ok(Self::Threshold2),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnAcoc {
     Disable = 0,
@@ -231,15 +280,24 @@ impl EnAcoc {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OcpSw1x {
     Threshold300mV = 0,
@@ -251,15 +309,24 @@ impl OcpSw1x {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Threshold300mV,
             _ => Self::Threshold450mV,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Threshold300mV),
+            1 => Ok(Self::Threshold450mV),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OcpSw2 {
     Threshold150mV = 0,
@@ -271,15 +338,24 @@ impl OcpSw2 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Threshold150mV,
             _ => Self::Threshold260mV,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Threshold150mV),
+            1 => Ok(Self::Threshold260mV),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnIchgIdchg {
     Discharge = 0,
@@ -291,15 +367,24 @@ impl EnIchgIdchg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Discharge,
             _ => Self::Charge,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Discharge),
+            1 => Ok(Self::Charge),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnExtIlim {
     Disable = 0,
@@ -311,15 +396,24 @@ impl EnExtIlim {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PkPwrTmax {
     Time20ms = 0,
@@ -329,11 +423,33 @@ pub enum PkPwrTmax {
 }
 
 impl PkPwrTmax {
+    /// Cycle time in milliseconds.
+    pub const fn millis(self) -> u16 {
+        match self {
+            Self::Time20ms => 20,
+            Self::Time40ms => 40,
+            Self::Time80ms => 80,
+            Self::Time1000ms => 1000,
+        }
+    }
+
+    /// Longest cycle time not above `ms`, or `None` if `ms` is below
+    /// the shortest one.
+    pub const fn from_millis(ms: u16) -> Option<Self> {
+        match ms {
+            0..=19 => None,
+            20..=39 => Some(Self::Time20ms),
+            40..=79 => Some(Self::Time40ms),
+            80..=999 => Some(Self::Time80ms),
+            _ => Some(Self::Time1000ms),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time20ms,
             1 => Self::Time40ms,
@@ -341,9 +457,20 @@ impl PkPwrTmax {
             _ => Self::Time1000ms,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time20ms),
+            1 => Ok(Self::Time40ms),
+            2 => Ok(Self::Time80ms),
+            3 => Ok(Self::Time1000ms),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatPkPwrRelax {
     NotInRelaxation = 0,
@@ -355,15 +482,24 @@ impl StatPkPwrRelax {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotInRelaxation,
             _ => Self::InRelaxation,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotInRelaxation),
+            1 => Ok(Self::InRelaxation),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatPkPwrOvld {
     NotInPeak = 0,
@@ -375,15 +511,24 @@ impl StatPkPwrOvld {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotInPeak,
             _ => Self::InPeak,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotInPeak),
+            1 => Ok(Self::InPeak),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnPkPwrVsys {
     Disable = 0,
@@ -395,15 +540,24 @@ impl EnPkPwrVsys {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnPkPwrIinDpm {
     Disable = 0,
@@ -415,15 +569,24 @@ impl EnPkPwrIinDpm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PkPwrTovldDeg {
     Time1ms = 0,
@@ -433,11 +596,33 @@ pub enum PkPwrTovldDeg {
 }
 
 impl PkPwrTovldDeg {
+    /// Overload time in milliseconds.
+    pub const fn millis(self) -> u16 {
+        match self {
+            Self::Time1ms => 1,
+            Self::Time2ms => 2,
+            Self::Time5ms => 5,
+            Self::Time10ms => 10,
+        }
+    }
+
+    /// Longest overload time not above `ms`, or `None` if `ms` is
+    /// below the shortest one.
+    pub const fn from_millis(ms: u16) -> Option<Self> {
+        match ms {
+            0 => None,
+            1 => Some(Self::Time1ms),
+            2..=4 => Some(Self::Time2ms),
+            5..=9 => Some(Self::Time5ms),
+            _ => Some(Self::Time10ms),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time1ms,
             1 => Self::Time2ms,
@@ -445,4 +630,14 @@ impl PkPwrTovldDeg {
             _ => Self::Time10ms,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time1ms),
+            1 => Ok(Self::Time2ms),
+            2 => Ok(Self::Time5ms),
+            3 => Ok(Self::Time10ms),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,10 +1,10 @@
 use bitfield_struct::bitfield;
 
-use super::RegReset;
+use super::{InvalidBits, RegReset};
 
 /// Charge Option 3 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeOption3 {
     /// PSYS definition during OTG mode.
     //
@@ -39,7 +39,7 @@ pub struct ChargeOption3 {
     /// 10b = 24A
     ///
     /// 11b = Disable (internal 30A limit)
-    #[bits(2, default = IlAvg::Disable)]
+    #[bits(2, default = IlAvg::Limit24A)]
     pub il_avg: IlAvg,
 
     /// The selection of the external EN_OTG pin control.
@@ -145,7 +145,26 @@ impl ChargeOption3 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargeOption3 {
+    psys_otg_idchg: PsysOtgIdchg => (PSYS_OTG_IDCHG_OFFSET, PSYS_OTG_IDCHG_BITS),
+    batfetoff_hiz: BatFetOffHiZ => (BATFETOFF_HIZ_OFFSET, BATFETOFF_HIZ_BITS),
+    cmp_en: CmpEn => (CMP_EN_OFFSET, CMP_EN_BITS),
+    il_avg: IlAvg => (IL_AVG_OFFSET, IL_AVG_BITS),
+    otg_vap_mode: OtgVapMode => (OTG_VAP_MODE_OFFSET, OTG_VAP_MODE_BITS),
+    batfet_enz: BatFetEnZ => (BATFET_ENZ_OFFSET, BATFET_ENZ_BITS),
+    en_vsys_min_soft_sr: EnVsysMinSoftSr => (EN_VSYS_MIN_SOFT_SR_OFFSET, EN_VSYS_MIN_SOFT_SR_BITS),
+    en_port_ctrl: EnPortCtrl => (EN_PORT_CTRL_OFFSET, EN_PORT_CTRL_BITS),
+    en_ico_mode: EnIcoMode => (EN_ICO_MODE_OFFSET, EN_ICO_MODE_BITS),
+    en_otg: EnOtg => (EN_OTG_OFFSET, EN_OTG_BITS),
+    detect_vindpm: DetectVinDpm => (DETECT_VINDPM_OFFSET, DETECT_VINDPM_BITS),
+    reg_reset: RegReset => (REG_RESET_OFFSET, REG_RESET_BITS),
+    en_hiz: EnHiZ => (EN_HIZ_OFFSET, EN_HIZ_BITS),
+});
+
+impl_register!(ChargeOption3, 0x0534, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PsysOtgIdchg {
     BatteryMinusOtg = 0,
@@ -157,15 +176,24 @@ impl PsysOtgIdchg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::BatteryMinusOtg,
             _ => Self::BatteryOnly,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::BatteryMinusOtg),
+            1 => Ok(Self::BatteryOnly),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatFetOffHiZ {
     Off = 0,
@@ -177,15 +205,24 @@ impl BatFetOffHiZ {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Off,
             _ => Self::On,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Off),
+            1 => Ok(Self::On),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum CmpEn {
     Disable = 0,
@@ -197,15 +234,24 @@ impl CmpEn {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IlAvg {
     Limit10A = 0,
@@ -219,7 +265,7 @@ impl IlAvg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Limit10A,
             1 => Self::Limit18A,
@@ -227,9 +273,20 @@ impl IlAvg {
             _ => Self::Disable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Limit10A),
+            1 => Ok(Self::Limit18A),
+            2 => Ok(Self::Limit24A),
+            3 => Ok(Self::Disable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OtgVapMode {
     VapMode = 0,
@@ -241,15 +298,24 @@ impl OtgVapMode {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::VapMode,
             _ => Self::OtgMode,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::VapMode),
+            1 => Ok(Self::OtgMode),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatFetEnZ {
     NoForceOff = 0,
@@ -261,15 +327,24 @@ impl BatFetEnZ {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NoForceOff,
             _ => Self::ForceOff,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NoForceOff),
+            1 => Ok(Self::ForceOff),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnVsysMinSoftSr {
     Disable = 0,
@@ -283,7 +358,7 @@ impl EnVsysMinSoftSr {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             1 => Self::SlewRate6_25,
@@ -291,9 +366,20 @@ impl EnVsysMinSoftSr {
             _ => Self::SlewRate1_5625,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::SlewRate6_25),
+            2 => Ok(Self::SlewRate3_125),
+            3 => Ok(Self::SlewRate1_5625),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnPortCtrl {
     Disable = 0,
@@ -305,15 +391,24 @@ impl EnPortCtrl {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnIcoMode {
     Disable = 0,
@@ -325,15 +420,24 @@ impl EnIcoMode {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnOtg {
     Disable = 0,
@@ -345,15 +449,24 @@ impl EnOtg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum DetectVinDpm {
     Idle = 0,
@@ -365,15 +478,24 @@ impl DetectVinDpm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Idle,
             _ => Self::Measure,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Idle),
+            1 => Ok(Self::Measure),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnHiZ {
     Disable = 0,
@@ -385,10 +507,18 @@ impl EnHiZ {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Charge Option 0 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeOption4 {
     /// PTM operation status bit monitor.
     ///
@@ -128,7 +130,23 @@ impl ChargeOption4 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargeOption4 {
+    stat_ptm: StatPtm => (STAT_PTM_OFFSET, STAT_PTM_BITS),
+    stat_idchg2: StatIdchg2 => (STAT_IDCHG2_OFFSET, STAT_IDCHG2_BITS),
+    pp_idchg2: PPIdchg2 => (PP_IDCHG2_OFFSET, PP_IDCHG2_BITS),
+    idchg_th2: Idchg2Threshold => (IDCHG_TH2_OFFSET, IDCHG_TH2_BITS),
+    idchg_deg2: IdchgDeg2 => (IDCHG_DEG2_OFFSET, IDCHG_DEG2_BITS),
+    stat_vbus_vap: StatVbusVap => (STAT_VBUS_VAP_OFFSET, STAT_VBUS_VAP_BITS),
+    pp_vbus_vap: PPVbusVap => (PP_VBUS_VAP_OFFSET, PP_VBUS_VAP_BITS),
+    vsys_uvp_no_hiccup: Hiccup => (VSYS_UVP_NO_HICCUP_OFFSET, VSYS_UVP_NO_HICCUP_BITS),
+    en_dither: Dither => (EN_DITHER_OFFSET, EN_DITHER_BITS),
+    vsys_uvp: VsysUvp => (VSYS_UVP_OFFSET, VSYS_UVP_BITS),
+});
+
+impl_register!(ChargeOption4, 0x0048, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatPtm {
     Inactive = 0,
@@ -140,15 +158,24 @@ impl StatPtm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Inactive,
             _ => Self::Active,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Inactive),
+            1 => Ok(Self::Active),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatIdchg2 {
     NotTriggered = 0,
@@ -160,15 +187,24 @@ impl StatIdchg2 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPIdchg2 {
     Disable = 0,
@@ -180,15 +216,24 @@ impl PPIdchg2 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Idchg2Threshold {
     Threshold125 = 0,
@@ -202,11 +247,41 @@ pub enum Idchg2Threshold {
 }
 
 impl Idchg2Threshold {
+    /// Threshold as a percentage of IDCHG_TH1.
+    pub const fn percent(self) -> u16 {
+        match self {
+            Self::Threshold125 => 125,
+            Self::Threshold150 => 150,
+            Self::Threshold175 => 175,
+            Self::Threshold200 => 200,
+            Self::Threshold250 => 250,
+            Self::Threshold300 => 300,
+            Self::Threshold350 => 350,
+            Self::Threshold400 => 400,
+        }
+    }
+
+    /// Highest threshold not above `percent` of IDCHG_TH1, or `None` if
+    /// `percent` is below the lowest one.
+    pub const fn from_percent(percent: u16) -> Option<Self> {
+        match percent {
+            0..=124 => None,
+            125..=149 => Some(Self::Threshold125),
+            150..=174 => Some(Self::Threshold150),
+            175..=199 => Some(Self::Threshold175),
+            200..=249 => Some(Self::Threshold200),
+            250..=299 => Some(Self::Threshold250),
+            300..=349 => Some(Self::Threshold300),
+            350..=399 => Some(Self::Threshold350),
+            _ => Some(Self::Threshold400),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Threshold125,
             1 => Self::Threshold150,
@@ -218,9 +293,24 @@ impl Idchg2Threshold {
             _ => Self::Threshold400,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Threshold125),
+            1 => Ok(Self::Threshold150),
+            2 => Ok(Self::Threshold175),
+            3 => Ok(Self::Threshold200),
+            4 => Ok(Self::Threshold250),
+            5 => Ok(Self::Threshold300),
+            6 => Ok(Self::Threshold350),
+            7 => Ok(Self::Threshold400),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IdchgDeg2 {
     Time98us = 0,
@@ -230,11 +320,33 @@ pub enum IdchgDeg2 {
 }
 
 impl IdchgDeg2 {
+    /// Typical deglitch time in microseconds.
+    pub const fn micros(self) -> u32 {
+        match self {
+            Self::Time98us => 98,
+            Self::Time1550us => 1550,
+            Self::Time6250us => 6250,
+            Self::Time12500us => 12500,
+        }
+    }
+
+    /// Longest deglitch time not above `us`, or `None` if `us` is below
+    /// the shortest one.
+    pub const fn from_micros(us: u32) -> Option<Self> {
+        match us {
+            0..=97 => None,
+            98..=1549 => Some(Self::Time98us),
+            1550..=6249 => Some(Self::Time1550us),
+            6250..=12499 => Some(Self::Time6250us),
+            _ => Some(Self::Time12500us),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time98us,
             1 => Self::Time1550us,
@@ -242,9 +354,20 @@ impl IdchgDeg2 {
             _ => Self::Time12500us,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time98us),
+            1 => Ok(Self::Time1550us),
+            2 => Ok(Self::Time6250us),
+            3 => Ok(Self::Time12500us),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatVbusVap {
     NotTriggered = 0,
@@ -256,15 +379,24 @@ impl StatVbusVap {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPVbusVap {
     Disable = 0,
@@ -276,15 +408,24 @@ impl PPVbusVap {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Hiccup {
     Disable = 0,
@@ -296,15 +437,24 @@ impl Hiccup {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Dither {
     Disable = 0,
@@ -318,7 +468,7 @@ impl Dither {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             1 => Self::Dither1x,
@@ -326,9 +476,20 @@ impl Dither {
             _ => Self::Dither3x,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Dither1x),
+            2 => Ok(Self::Dither2x),
+            3 => Ok(Self::Dither3x),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum VsysUvp {
     MilliVolt2400,
@@ -346,7 +507,7 @@ impl VsysUvp {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::MilliVolt2400,
             1 => Self::MilliVolt3200,
@@ -358,4 +519,18 @@ impl VsysUvp {
             _ => Self::MilliVolt8000,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::MilliVolt2400),
+            1 => Ok(Self::MilliVolt3200),
+            2 => Ok(Self::MilliVolt4000),
+            3 => Ok(Self::MilliVolt4800),
+            4 => Ok(Self::MilliVolt5600),
+            5 => Ok(Self::MilliVolt6400),
+            6 => Ok(Self::MilliVolt7200),
+            7 => Ok(Self::MilliVolt8000),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
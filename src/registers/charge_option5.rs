@@ -1,10 +1,10 @@
 use bitfield_struct::bitfield;
 
-use super::WdRst;
+use super::{InvalidBits, WdRst};
 
 /// Charge Option 0 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeOption5 {
     /// Adjust dual phase to single phase (phase dropping transition)
     /// deglitch time.
@@ -138,7 +138,24 @@ impl ChargeOption5 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargeOption5 {
+    ph_drop_deg: PhDropDeg => (PH_DROP_DEG_OFFSET, PH_DROP_DEG_BITS),
+    ph_add_deg: PhAddDeg => (PH_ADD_DEG_OFFSET, PH_ADD_DEG_BITS),
+    force_single: ForceSingle => (FORCE_SINGLE_OFFSET, FORCE_SINGLE_BITS),
+    single_dual_trans_th: BuckTransitionThreshold => (SINGLE_DUAL_TRANS_TH_OFFSET, SINGLE_DUAL_TRANS_TH_BITS),
+    high_duty_buck: HighDutyBuck => (HIGH_DUTY_BUCK_OFFSET, HIGH_DUTY_BUCK_BITS),
+    batcoc_config: BatCocConfig => (BATCOC_CONFIG_OFFSET, BATCOC_CONFIG_BITS),
+    en_reg_lwpwr: RegnLwPwr => (EN_REG_LWPWR_OFFSET, EN_REG_LWPWR_BITS),
+    regn_ext: RegnExt => (REGN_EXT_OFFSET, REGN_EXT_BITS),
+    cmpin_tr_select: Function => (CMPIN_TR_SELECT_OFFSET, CMPIN_TR_SELECT_BITS),
+    wd_rst: WdRst => (WD_RST_OFFSET, WD_RST_BITS),
+    ptm_exit_light_load: PtmAutoExit => (PTM_EXIT_LIGHT_LOAD_OFFSET, PTM_EXIT_LIGHT_LOAD_BITS),
+});
+
+impl_register!(ChargeOption5, 0x0685, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PhDropDeg {
     Time93us,
@@ -148,11 +165,33 @@ pub enum PhDropDeg {
 }
 
 impl PhDropDeg {
+    /// Typical deglitch time in microseconds.
+    pub const fn micros(self) -> u32 {
+        match self {
+            Self::Time93us => 93,
+            Self::Time1500us => 1500,
+            Self::Time11000us => 11000,
+            Self::Time94000us => 94000,
+        }
+    }
+
+    /// Longest deglitch time not above `us`, or `None` if `us` is below
+    /// the shortest one.
+    pub const fn from_micros(us: u32) -> Option<Self> {
+        match us {
+            0..=92 => None,
+            93..=1499 => Some(Self::Time93us),
+            1500..=10999 => Some(Self::Time1500us),
+            11000..=93999 => Some(Self::Time11000us),
+            _ => Some(Self::Time94000us),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time93us,
             1 => Self::Time1500us,
@@ -160,9 +199,20 @@ impl PhDropDeg {
             _ => Self::Time94000us,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time93us),
+            1 => Ok(Self::Time1500us),
+            2 => Ok(Self::Time11000us),
+            3 => Ok(Self::Time94000us),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PhAddDeg {
     Time1700ns,
@@ -172,11 +222,33 @@ pub enum PhAddDeg {
 }
 
 impl PhAddDeg {
+    /// Typical deglitch time in nanoseconds.
+    pub const fn nanos(self) -> u32 {
+        match self {
+            Self::Time1700ns => 1700,
+            Self::Time5500ns => 5500,
+            Self::Time20000ns => 20000,
+            Self::Time86000ns => 86000,
+        }
+    }
+
+    /// Longest deglitch time not above `ns`, or `None` if `ns` is below
+    /// the shortest one.
+    pub const fn from_nanos(ns: u32) -> Option<Self> {
+        match ns {
+            0..=1699 => None,
+            1700..=5499 => Some(Self::Time1700ns),
+            5500..=19999 => Some(Self::Time5500ns),
+            20000..=85999 => Some(Self::Time20000ns),
+            _ => Some(Self::Time86000ns),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time1700ns,
             1 => Self::Time5500ns,
@@ -184,9 +256,20 @@ impl PhAddDeg {
             _ => Self::Time86000ns,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time1700ns),
+            1 => Ok(Self::Time5500ns),
+            2 => Ok(Self::Time20000ns),
+            3 => Ok(Self::Time86000ns),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ForceSingle {
     Disable,
@@ -198,15 +281,24 @@ impl ForceSingle {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BuckTransitionThreshold {
     ForceDual,
@@ -220,11 +312,41 @@ pub enum BuckTransitionThreshold {
 }
 
 impl BuckTransitionThreshold {
+    /// Single to dual phase load current threshold in amps, or `None`
+    /// for forced dual phase.
+    pub const fn amps(self) -> Option<u8> {
+        match self {
+            Self::ForceDual => None,
+            Self::Current3A => Some(3),
+            Self::Current4A => Some(4),
+            Self::Current5A => Some(5),
+            Self::Current6A => Some(6),
+            Self::Current7A => Some(7),
+            Self::Current8A => Some(8),
+            Self::Current9A => Some(9),
+        }
+    }
+
+    /// Threshold for a load current in amps, or `None` if it is outside
+    /// 3A to 9A.
+    pub const fn from_amps(amps: u8) -> Option<Self> {
+        match amps {
+            3 => Some(Self::Current3A),
+            4 => Some(Self::Current4A),
+            5 => Some(Self::Current5A),
+            6 => Some(Self::Current6A),
+            7 => Some(Self::Current7A),
+            8 => Some(Self::Current8A),
+            9 => Some(Self::Current9A),
+            _ => None,
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::ForceDual,
             1 => Self::Current3A,
@@ -236,9 +358,24 @@ impl BuckTransitionThreshold {
             _ => Self::Current9A,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::ForceDual),
+            1 => Ok(Self::Current3A),
+            2 => Ok(Self::Current4A),
+            3 => Ok(Self::Current5A),
+            4 => Ok(Self::Current6A),
+            5 => Ok(Self::Current7A),
+            6 => Ok(Self::Current8A),
+            7 => Ok(Self::Current9A),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum HighDutyBuck {
     Disable,
@@ -250,15 +387,24 @@ impl HighDutyBuck {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatCocConfig {
     Disable,
@@ -272,7 +418,7 @@ impl BatCocConfig {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             1 => Self::MiliVolts50,
@@ -280,9 +426,20 @@ impl BatCocConfig {
             _ => Self::MiliVolts100,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::MiliVolts50),
+            2 => Ok(Self::MiliVolts75),
+            3 => Ok(Self::MiliVolts100),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum RegnLwPwr {
     Disable,
@@ -294,15 +451,24 @@ impl RegnLwPwr {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum RegnExt {
     Disable,
@@ -314,15 +480,24 @@ impl RegnExt {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Function {
     Cmpin,
@@ -334,15 +509,24 @@ impl Function {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Cmpin,
             _ => Self::Treg,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Cmpin),
+            1 => Ok(Self::Treg),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PtmAutoExit {
     Disable,
@@ -354,10 +538,18 @@ impl PtmAutoExit {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Charge Profile Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeProfile {
     /// Termination current setting with 5mΩ sense resistor.
     ///
@@ -39,3 +39,7 @@ impl ChargeProfile {
         0x17
     }
 }
+
+impl_try_from_bits!(ChargeProfile {});
+
+impl_register!(ChargeProfile, 0x3020, ReadWrite);
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Charge Voltage Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargeVoltage {
     #[bits(2, default = 0)]
     reserved1_0: u8,
@@ -26,7 +26,40 @@ pub struct ChargeVoltage {
 }
 
 impl ChargeVoltage {
+    /// Lowest non-zero charge voltage, in millivolts.
+    pub const MIN_MV: u16 = 5000;
+
+    /// Highest charge voltage, in millivolts.
+    pub const MAX_MV: u16 = 23000;
+
+    const STEP_MV: u16 = 4;
+
     pub(crate) const fn addr() -> u8 {
         0x15
     }
+
+    /// Charge voltage in millivolts.
+    pub const fn millivolts(&self) -> u16 {
+        self.voltage() * Self::STEP_MV
+    }
+
+    /// Sets the charge voltage in millivolts. Non-zero values are
+    /// clamped to the register range, like the charger itself does.
+    pub const fn with_millivolts(self, mv: u16) -> Self {
+        let mv = if mv == 0 {
+            0
+        } else if mv < Self::MIN_MV {
+            Self::MIN_MV
+        } else if mv > Self::MAX_MV {
+            Self::MAX_MV
+        } else {
+            mv
+        };
+
+        self.with_voltage(mv / Self::STEP_MV)
+    }
 }
+
+impl_try_from_bits!(ChargeVoltage {});
+
+impl_register!(ChargeVoltage, 0x0000, ReadWrite);
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Charger Status 0 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargerStatus0 {
     #[bits(3, default = 0)]
     reserved2_0: u16,
@@ -114,7 +116,20 @@ impl ChargerStatus0 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ChargerStatus0 {
+    fault_regn: Fault => (FAULT_REGN_OFFSET, FAULT_REGN_BITS),
+    fault_ocp: Fault => (FAULT_OCP_OFFSET, FAULT_OCP_BITS),
+    fault_batovp: Fault => (FAULT_BATOVP_OFFSET, FAULT_BATOVP_BITS),
+    mode_stat: ModeStat => (MODE_STAT_OFFSET, MODE_STAT_BITS),
+    treg_stat: TRegStat => (TREG_STAT_OFFSET, TREG_STAT_BITS),
+    chg_tmr_stat: ChgTmrStat => (CHG_TMR_STAT_OFFSET, CHG_TMR_STAT_BITS),
+    chrg_stat: ChrgStat => (CHRG_STAT_OFFSET, CHRG_STAT_BITS),
+});
+
+impl_register!(ChargerStatus0, 0x0000, ReadOnly);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Fault {
     NoFault,
@@ -126,15 +141,24 @@ impl Fault {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NoFault,
             _ => Self::Fault,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NoFault),
+            1 => Ok(Self::Fault),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ModeStat {
     QuasiDualNormalCompFsw600kHz,
@@ -148,25 +172,51 @@ pub enum ModeStat {
 }
 
 impl ModeStat {
+    /// Whether the MODE pin selects quasi dual phase operation.
+    pub const fn is_quasi_dual(self) -> bool {
+        matches!(
+            self,
+            Self::QuasiDualNormalCompFsw600kHz
+                | Self::QuasiDualNormalCompFsw800kHz
+                | Self::QuasiDualSlowCompFsw600kHz
+                | Self::QuasiDualSlowCompFsw800kHz
+        )
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::QuasiDualNormalCompFsw600kHz,
             1 => Self::QuasiDualNormalCompFsw800kHz,
-            2 => Self::QuasiDualNormalCompFsw600kHz,
-            3 => Self::QuasiDualNormalCompFsw800kHz,
+            2 => Self::QuasiDualSlowCompFsw600kHz,
+            3 => Self::QuasiDualSlowCompFsw800kHz,
             4 => Self::NormalCompFsw600kHz,
             5 => Self::NormalCompFsw800kHz,
             6 => Self::SlowCompFsw600kHz,
             _ => Self::SlowCompFsw800kHz,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::QuasiDualNormalCompFsw600kHz),
+            1 => Ok(Self::QuasiDualNormalCompFsw800kHz),
+            2 => Ok(Self::QuasiDualSlowCompFsw600kHz),
+            3 => Ok(Self::QuasiDualSlowCompFsw800kHz),
+            4 => Ok(Self::NormalCompFsw600kHz),
+            5 => Ok(Self::NormalCompFsw800kHz),
+            6 => Ok(Self::SlowCompFsw600kHz),
+            7 => Ok(Self::SlowCompFsw800kHz),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum TRegStat {
     NotRegulating,
@@ -178,15 +228,24 @@ impl TRegStat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotRegulating,
             _ => Self::Regulating,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotRegulating),
+            1 => Ok(Self::Regulating),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ChgTmrStat {
     Normal,
@@ -198,15 +257,24 @@ impl ChgTmrStat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Normal,
             _ => Self::Expired,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Normal),
+            1 => Ok(Self::Expired),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ChrgStat {
     NotCharging = 0,
@@ -222,7 +290,7 @@ impl ChrgStat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotCharging,
             1 => Self::TrickleCharge,
@@ -232,4 +300,16 @@ impl ChrgStat {
             _ => Self::ChargeTerminationDone,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotCharging),
+            1 => Ok(Self::TrickleCharge),
+            2 => Ok(Self::PreCharge),
+            3 => Ok(Self::FastChargeCC),
+            4 => Ok(Self::FastChargeCV),
+            7 => Ok(Self::ChargeTerminationDone),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Charger Status 1 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ChargerStatus1 {
     #[bits(16, default = 0)]
     val: u16,
@@ -13,3 +13,7 @@ impl ChargerStatus1 {
         0x20
     }
 }
+
+impl_try_from_bits!(ChargerStatus1 {});
+
+impl_register!(ChargerStatus1, 0x0000, ReadWrite);
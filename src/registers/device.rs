@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Device ID Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct Device {
     #[bits(8, default = 0x0a)]
     pub id: u8,
@@ -16,3 +16,7 @@ impl Device {
         0xff
     }
 }
+
+impl_try_from_bits!(Device {});
+
+impl_register!(Device, 0x000a, ReadOnly);
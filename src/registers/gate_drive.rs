@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Gate Drive Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct GateDrive {
     #[bits(1, default = false)]
     reserved0: bool,
@@ -125,7 +127,19 @@ impl GateDrive {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(GateDrive {
+    vsys_reg_slow: VsysRegSlow => (VSYS_REG_SLOW_OFFSET, VSYS_REG_SLOW_BITS),
+    lodrv2_stat: DrvStat => (LODRV2_STAT_OFFSET, LODRV2_STAT_BITS),
+    hidrv2_stat: DrvStat => (HIDRV2_STAT_OFFSET, HIDRV2_STAT_BITS),
+    batovp_extend: BatOvpExtend => (BATOVP_EXTEND_OFFSET, BATOVP_EXTEND_BITS),
+    lodrv1_stat: DrvStat => (LODRV1_STAT_OFFSET, LODRV1_STAT_BITS),
+    hidrv1_stat: DrvStat => (HIDRV1_STAT_OFFSET, HIDRV1_STAT_BITS),
+});
+
+impl_register!(GateDrive, 0x246c, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum VsysRegSlow {
     Disable = 0,
@@ -137,15 +151,24 @@ impl VsysRegSlow {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum DrvStat {
     Scale0 = 0,
@@ -163,7 +186,7 @@ impl DrvStat {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Scale0,
             1 => Self::Scale1,
@@ -175,9 +198,24 @@ impl DrvStat {
             _ => Self::Scale7,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Scale0),
+            1 => Ok(Self::Scale1),
+            2 => Ok(Self::Scale2),
+            3 => Ok(Self::Scale3),
+            4 => Ok(Self::Scale4),
+            5 => Ok(Self::Scale5),
+            6 => Ok(Self::Scale6),
+            7 => Ok(Self::Scale7),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatOvpExtend {
     Disable = 0,
@@ -189,10 +227,18 @@ impl BatOvpExtend {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// GM Adjust Force Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct GmAdjustForce {
     /// Enable FORCE_AUTOTUNE_A, FORCE_AUTOTUNE_B effective for
     /// inductor DCR current sense.
@@ -75,7 +77,16 @@ impl GmAdjustForce {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(GmAdjustForce {
+    force_autotune_en: ForceAutotune => (FORCE_AUTOTUNE_EN_OFFSET, FORCE_AUTOTUNE_EN_BITS),
+    force_gm_adjust_en: ForceGmAdjust => (FORCE_GM_ADJUST_EN_OFFSET, FORCE_GM_ADJUST_EN_BITS),
+    force_update: ForceUpdate => (FORCE_UPDATE_OFFSET, FORCE_UPDATE_BITS),
+});
+
+impl_register!(GmAdjustForce, 0x00c7, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ForceAutotune {
     Disable,
@@ -87,15 +98,24 @@ impl ForceAutotune {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ForceGmAdjust {
     Disable,
@@ -107,15 +127,24 @@ impl ForceGmAdjust {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ForceUpdate {
     Idle,
@@ -127,10 +156,18 @@ impl ForceUpdate {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Idle,
             _ => Self::Update,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Idle),
+            1 => Ok(Self::Update),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
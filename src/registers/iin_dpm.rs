@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::RsnsRac;
+
 /// Iin Dpm Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct IinDpm {
     #[bits(2, default = 0)]
     reserved1_0: u16,
@@ -24,8 +26,21 @@ pub struct IinDpm {
     #[bits(5, default = 0)]
     reserved15_11: u16,
 }
+
 impl IinDpm {
+    const STEP_MA: u16 = 25;
+
     pub(crate) const fn addr() -> u8 {
         0x22
     }
+
+    /// Input current limit in milliamps for the given RAC sense
+    /// resistor.
+    pub const fn milliamps(&self, rac: RsnsRac) -> u16 {
+        self.current() * rac.scale(Self::STEP_MA)
+    }
 }
+
+impl_try_from_bits!(IinDpm {});
+
+impl_register!(IinDpm, 0x0320, ReadOnly);
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::RsnsRac;
+
 /// Iin Host Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct IinHost {
     #[bits(2, default = 0)]
     reserved1_0: u16,
@@ -29,7 +31,38 @@ pub struct IinHost {
 }
 
 impl IinHost {
+    const STEP_MA: u16 = 25;
+    const MIN: u16 = 0x10;
+    const MAX: u16 = 0x148;
+
     pub(crate) const fn addr() -> u8 {
         0x3f
     }
+
+    /// Input current limit in milliamps for the given RAC sense
+    /// resistor.
+    pub const fn milliamps(&self, rac: RsnsRac) -> u16 {
+        self.current() * rac.scale(Self::STEP_MA)
+    }
+
+    /// Sets the input current limit in milliamps for the given RAC
+    /// sense resistor, clamped to the register range like the charger
+    /// itself does.
+    pub const fn with_milliamps(self, ma: u16, rac: RsnsRac) -> Self {
+        let current = ma / rac.scale(Self::STEP_MA);
+
+        let current = if current < Self::MIN {
+            Self::MIN
+        } else if current > Self::MAX {
+            Self::MAX
+        } else {
+            current
+        };
+
+        self.with_current(current)
+    }
 }
+
+impl_try_from_bits!(IinHost {});
+
+impl_register!(IinHost, 0x0320, ReadWrite);
@@ -1,10 +1,10 @@
 use bitfield_struct::bitfield;
 
 /// Manufacturer ID Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct Manufacturer {
-    #[bits(8, default = 0x0a)]
+    #[bits(8, default = 0x40)]
     pub id: u8,
 
     #[bits(8, default = 0)]
@@ -16,3 +16,7 @@ impl Manufacturer {
         0xfe
     }
 }
+
+impl_try_from_bits!(Manufacturer {});
+
+impl_register!(Manufacturer, 0x0040, ReadOnly);
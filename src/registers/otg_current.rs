@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::RsnsRac;
+
 /// Otg Current Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct OtgCurrent {
     #[bits(2, default = 0)]
     reserved1_0: u8,
@@ -29,7 +31,49 @@ pub struct OtgCurrent {
 }
 
 impl OtgCurrent {
+    const STEP_MA: u16 = 25;
+    const MIN: u16 = 0x4;
+    const MAX: u16 = 0x78;
+
     pub(crate) const fn addr() -> u8 {
         0x3c
     }
+
+    /// Lowest OTG current limit in milliamps for the given RAC sense
+    /// resistor.
+    pub const fn min_milliamps(rac: RsnsRac) -> u16 {
+        Self::MIN * rac.scale(Self::STEP_MA)
+    }
+
+    /// Highest OTG current limit in milliamps for the given RAC sense
+    /// resistor.
+    pub const fn max_milliamps(rac: RsnsRac) -> u16 {
+        Self::MAX * rac.scale(Self::STEP_MA)
+    }
+
+    /// OTG current limit in milliamps for the given RAC sense resistor.
+    pub const fn milliamps(&self, rac: RsnsRac) -> u16 {
+        self.current() * rac.scale(Self::STEP_MA)
+    }
+
+    /// Sets the OTG current limit in milliamps for the given RAC sense
+    /// resistor, clamped to the register range like the charger itself
+    /// does.
+    pub const fn with_milliamps(self, ma: u16, rac: RsnsRac) -> Self {
+        let current = ma / rac.scale(Self::STEP_MA);
+
+        let current = if current < Self::MIN {
+            Self::MIN
+        } else if current > Self::MAX {
+            Self::MAX
+        } else {
+            current
+        };
+
+        self.with_current(current)
+    }
 }
+
+impl_try_from_bits!(OtgCurrent {});
+
+impl_register!(OtgCurrent, 0x01e0, ReadWrite);
@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Otg Voltage Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct OtgVoltage {
     #[bits(2, default = 0)]
     reserved1_0: u8,
@@ -29,7 +29,38 @@ pub struct OtgVoltage {
 }
 
 impl OtgVoltage {
+    /// Lowest OTG voltage, in millivolts.
+    pub const MIN_MV: u16 = 3000;
+
+    /// Highest OTG voltage, in millivolts.
+    pub const MAX_MV: u16 = 5000;
+
+    const STEP_MV: u16 = 20;
+
     pub(crate) const fn addr() -> u8 {
         0x3b
     }
+
+    /// OTG voltage in millivolts.
+    pub const fn millivolts(&self) -> u16 {
+        self.voltage() * Self::STEP_MV
+    }
+
+    /// Sets the OTG voltage in millivolts, clamped to the register
+    /// range like the charger itself does.
+    pub const fn with_millivolts(self, mv: u16) -> Self {
+        let mv = if mv < Self::MIN_MV {
+            Self::MIN_MV
+        } else if mv > Self::MAX_MV {
+            Self::MAX_MV
+        } else {
+            mv
+        };
+
+        self.with_voltage(mv / Self::STEP_MV)
+    }
 }
+
+impl_try_from_bits!(OtgVoltage {});
+
+impl_register!(OtgVoltage, 0x03e8, ReadWrite);
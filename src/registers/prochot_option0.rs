@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Prochot Option 0
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ProchotOption0 {
     /// Enable lower threshold of PROCHOT_VINDPM comparator.
     ///
@@ -131,7 +133,18 @@ impl ProchotOption0 {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ProchotOption0 {
+    lower_prohot_vindpm: LowerProchotVinDpm => (LOWER_PROHOT_VINDPM_OFFSET, LOWER_PROHOT_VINDPM_BITS),
+    inom_deg: InomDeg => (INOM_DEG_OFFSET, INOM_DEG_BITS),
+    prochot_vindpm_80_90: ProchotVinDpm => (PROCHOT_VINDPM_80_90_OFFSET, PROCHOT_VINDPM_80_90_BITS),
+    icrit_deg: IcritDeg => (ICRIT_DEG_OFFSET, ICRIT_DEG_BITS),
+    ilim2_vth: Ilim2Vth => (ILIM2_VTH_OFFSET, ILIM2_VTH_BITS),
+});
+
+impl_register!(ProchotOption0, 0x4a39, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum LowerProchotVinDpm {
     Disable = 0,
@@ -143,15 +156,24 @@ impl LowerProchotVinDpm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum InomDeg {
     Time9880us = 0,
@@ -163,15 +185,24 @@ impl InomDeg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time9880us,
             _ => Self::Time64000us,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time9880us),
+            1 => Ok(Self::Time64000us),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ProchotVinDpm {
     VinDpm83 = 0,
@@ -183,15 +214,24 @@ impl ProchotVinDpm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::VinDpm83,
             _ => Self::VinDpm91,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::VinDpm83),
+            1 => Ok(Self::VinDpm91),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IcritDeg {
     Time14_5us = 0,
@@ -205,7 +245,7 @@ impl IcritDeg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time14_5us,
             1 => Self::Time111us,
@@ -213,9 +253,20 @@ impl IcritDeg {
             _ => Self::Time873us,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time14_5us),
+            1 => Ok(Self::Time111us),
+            2 => Ok(Self::Time443us),
+            3 => Ok(Self::Time873us),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Ilim2Vth {
     Percent110 = 1,
@@ -251,11 +302,35 @@ pub enum Ilim2Vth {
 }
 
 impl Ilim2Vth {
+    /// Threshold as a percentage of IIN_DPM.
+    pub const fn percent(self) -> u16 {
+        match self as u16 {
+            code @ 1..=25 => 105 + code * 5,
+            code => 250 + (code - 26) * 50,
+        }
+    }
+
+    /// Highest threshold not above `percent` of IIN_DPM, or `None` if
+    /// `percent` is below the lowest threshold.
+    pub const fn from_percent(percent: u16) -> Option<Self> {
+        let code = match percent {
+            0..=109 => return None,
+            110..=234 => (percent - 105) / 5,
+            235..=449 => 25 + (percent - 200) / 50,
+            _ => 30,
+        };
+
+        match Self::try_from_bits(code as u8) {
+            Ok(threshold) => Some(threshold),
+            Err(_) => None,
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             1 => Self::Percent110,
             2 => Self::Percent115,
@@ -289,4 +364,40 @@ impl Ilim2Vth {
             _ => Self::Percent450,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            1 => Ok(Self::Percent110),
+            2 => Ok(Self::Percent115),
+            3 => Ok(Self::Percent120),
+            4 => Ok(Self::Percent125),
+            5 => Ok(Self::Percent130),
+            6 => Ok(Self::Percent135),
+            7 => Ok(Self::Percent140),
+            8 => Ok(Self::Percent145),
+            9 => Ok(Self::Percent150),
+            10 => Ok(Self::Percent155),
+            11 => Ok(Self::Percent160),
+            12 => Ok(Self::Percent165),
+            13 => Ok(Self::Percent170),
+            14 => Ok(Self::Percent175),
+            15 => Ok(Self::Percent180),
+            16 => Ok(Self::Percent185),
+            17 => Ok(Self::Percent190),
+            18 => Ok(Self::Percent195),
+            19 => Ok(Self::Percent200),
+            20 => Ok(Self::Percent205),
+            21 => Ok(Self::Percent210),
+            22 => Ok(Self::Percent215),
+            23 => Ok(Self::Percent220),
+            24 => Ok(Self::Percent225),
+            25 => Ok(Self::Percent230),
+            26 => Ok(Self::Percent250),
+            27 => Ok(Self::Percent300),
+            28 => Ok(Self::Percent350),
+            29 => Ok(Self::Percent400),
+            30 => Ok(Self::Percent450),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::{InvalidBits, RsnsRsr};
+
 /// Prochot Option 1 Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ProchotOption1 {
     /// Adapter removal PROCHOT profile enable.
     ///
@@ -55,7 +57,7 @@ pub struct ProchotOption1 {
     /// 0b = Disable
     ///
     /// 1b = Enable
-    #[bits(1, default = PPIcrit::Disable)]
+    #[bits(1, default = PPIcrit::Enable)]
     pub pp_icrit: PPIcrit,
 
     /// COMP PROCHOT profile enable.
@@ -71,7 +73,7 @@ pub struct ProchotOption1 {
     /// 0b = Disable
     ///
     /// 1b = Enable
-    #[bits(1, default = PPVinDpm::Disable)]
+    #[bits(1, default = PPVinDpm::Enable)]
     pub pp_vindpm: PPVinDpm,
 
     /// IDCHG Deglitch Time.
@@ -110,12 +112,61 @@ pub struct ProchotOption1 {
 }
 
 impl ProchotOption1 {
+    const IDCHG_TH1_STEP_MA: u16 = 500;
+    const IDCHG_TH1_OFFSET_MA: u16 = 1500;
+    const IDCHG_TH1_MAX: u16 = 0x3f;
+
     pub(crate) const fn addr() -> u8 {
         0x34
     }
+
+    /// IDCHG level 1 threshold in milliamps for the given RSR sense
+    /// resistor.
+    pub const fn idchg_th1_ma(&self, rsr: RsnsRsr) -> u32 {
+        rsr.scale(Self::IDCHG_TH1_OFFSET_MA) as u32
+            + self.idchg_th1() as u32 * rsr.scale(Self::IDCHG_TH1_STEP_MA) as u32
+    }
+
+    /// Lowest IDCHG level 1 threshold in milliamps for the given RSR
+    /// sense resistor. Code 0 keeps PROCHOT always triggered, so the
+    /// lowest threshold is one step above the offset.
+    pub const fn idchg_th1_min_ma(rsr: RsnsRsr) -> u32 {
+        rsr.scale(Self::IDCHG_TH1_OFFSET_MA + Self::IDCHG_TH1_STEP_MA) as u32
+    }
+
+    /// Sets the IDCHG level 1 threshold in milliamps for the given RSR
+    /// sense resistor, rounded down and clamped to the register range.
+    /// Thresholds below [`Self::idchg_th1_min_ma`] encode to code 0.
+    pub const fn with_idchg_th1_ma(self, ma: u32, rsr: RsnsRsr) -> Self {
+        let offset = rsr.scale(Self::IDCHG_TH1_OFFSET_MA) as u32;
+        let code = ma.saturating_sub(offset) / rsr.scale(Self::IDCHG_TH1_STEP_MA) as u32;
+
+        let code = if code > Self::IDCHG_TH1_MAX as u32 {
+            Self::IDCHG_TH1_MAX
+        } else {
+            code as u16
+        };
+
+        self.with_idchg_th1(code)
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ProchotOption1 {
+    pp_acock: PPAcok => (PP_ACOCK_OFFSET, PP_ACOCK_BITS),
+    pp_batpres: PPBatPres => (PP_BATPRES_OFFSET, PP_BATPRES_BITS),
+    pp_vsys: PPVsys => (PP_VSYS_OFFSET, PP_VSYS_BITS),
+    pp_idchg1: PPIdchg1 => (PP_IDCHG1_OFFSET, PP_IDCHG1_BITS),
+    pp_inom: PPInom => (PP_INOM_OFFSET, PP_INOM_BITS),
+    pp_icrit: PPIcrit => (PP_ICRIT_OFFSET, PP_ICRIT_BITS),
+    pp_cmp: PPCmp => (PP_CMP_OFFSET, PP_CMP_BITS),
+    pp_vindpm: PPVinDpm => (PP_VINDPM_OFFSET, PP_VINDPM_BITS),
+    idchg_deg1: IdchgDeg1 => (IDCHG_DEG1_OFFSET, IDCHG_DEG1_BITS),
+});
+
+impl_register!(ProchotOption1, 0x41a0, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPAcok {
     Disable = 0,
@@ -127,15 +178,24 @@ impl PPAcok {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPBatPres {
     Disable = 0,
@@ -147,15 +207,24 @@ impl PPBatPres {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPVsys {
     Disable = 0,
@@ -167,15 +236,24 @@ impl PPVsys {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPIdchg1 {
     Disable = 0,
@@ -187,15 +265,24 @@ impl PPIdchg1 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPInom {
     Disable = 0,
@@ -207,15 +294,24 @@ impl PPInom {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPIcrit {
     Disable = 0,
@@ -227,15 +323,24 @@ impl PPIcrit {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPCmp {
     Disable = 0,
@@ -247,15 +352,24 @@ impl PPCmp {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum PPVinDpm {
     Disable = 0,
@@ -267,15 +381,24 @@ impl PPVinDpm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IdchgDeg1 {
     Time78ms = 0,
@@ -285,11 +408,33 @@ pub enum IdchgDeg1 {
 }
 
 impl IdchgDeg1 {
+    /// Typical deglitch time in milliseconds.
+    pub const fn millis(self) -> u32 {
+        match self {
+            Self::Time78ms => 78,
+            Self::Time1250ms => 1250,
+            Self::Time5000ms => 5000,
+            Self::Time20000ms => 20000,
+        }
+    }
+
+    /// Longest deglitch time not above `ms`, or `None` if `ms` is below
+    /// the shortest one.
+    pub const fn from_millis(ms: u32) -> Option<Self> {
+        match ms {
+            0..=77 => None,
+            78..=1249 => Some(Self::Time78ms),
+            1250..=4999 => Some(Self::Time1250ms),
+            5000..=19999 => Some(Self::Time5000ms),
+            _ => Some(Self::Time20000ms),
+        }
+    }
+
     const fn into_bits(self) -> u8 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Time78ms,
             1 => Self::Time1250ms,
@@ -297,4 +442,14 @@ impl IdchgDeg1 {
             _ => Self::Time20000ms,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Time78ms),
+            1 => Ok(Self::Time1250ms),
+            2 => Ok(Self::Time5000ms),
+            3 => Ok(Self::Time20000ms),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// Prochot Status Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct ProchotStatus {
     /// The status is latched until a read from host.
     ///
@@ -141,7 +143,27 @@ impl ProchotStatus {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(ProchotStatus {
+    stat_adapter_removal: StatAdapterRemoval => (STAT_ADAPTER_REMOVAL_OFFSET, STAT_ADAPTER_REMOVAL_BITS),
+    stat_battery_removal: StatBatteryRemoval => (STAT_BATTERY_REMOVAL_OFFSET, STAT_BATTERY_REMOVAL_BITS),
+    stat_vsys_: StatVsys => (STAT_VSYS__OFFSET, STAT_VSYS__BITS),
+    stat_idchg1_: StatIdchg1 => (STAT_IDCHG1__OFFSET, STAT_IDCHG1__BITS),
+    stat_inom_: StatInom => (STAT_INOM__OFFSET, STAT_INOM__BITS),
+    stat_icrit_: StatIcrit => (STAT_ICRIT__OFFSET, STAT_ICRIT__BITS),
+    stat_comp_: StatComp => (STAT_COMP__OFFSET, STAT_COMP__BITS),
+    stat_vindpm_: StatVinDpm => (STAT_VINDPM__OFFSET, STAT_VINDPM__BITS),
+    stat_exit_vap: StatExitVap => (STAT_EXIT_VAP_OFFSET, STAT_EXIT_VAP_BITS),
+    stat_vap_fail: StatVapFail => (STAT_VAP_FAIL_OFFSET, STAT_VAP_FAIL_BITS),
+    tshut: Tshut => (TSHUT_OFFSET, TSHUT_BITS),
+    prochot_clear: ProchotClear => (PROCHOT_CLEAR_OFFSET, PROCHOT_CLEAR_BITS),
+    prochot_width: ProchotWidth => (PROCHOT_WIDTH_OFFSET, PROCHOT_WIDTH_BITS),
+    en_prochot_ext: EnProchotExt => (EN_PROCHOT_EXT_OFFSET, EN_PROCHOT_EXT_BITS),
+});
+
+impl_register!(ProchotStatus, 0x3800, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatAdapterRemoval {
     NotTriggered = 0,
@@ -153,15 +175,24 @@ impl StatAdapterRemoval {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatBatteryRemoval {
     NotTriggered = 0,
@@ -173,15 +204,24 @@ impl StatBatteryRemoval {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatVsys {
     NotTriggered = 0,
@@ -193,15 +233,24 @@ impl StatVsys {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatIdchg1 {
     NotTriggered = 0,
@@ -213,15 +262,24 @@ impl StatIdchg1 {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatInom {
     NotTriggered = 0,
@@ -233,15 +291,24 @@ impl StatInom {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatIcrit {
     NotTriggered = 0,
@@ -253,15 +320,24 @@ impl StatIcrit {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatComp {
     NotTriggered = 0,
@@ -273,15 +349,24 @@ impl StatComp {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatVinDpm {
     NotTriggered = 0,
@@ -293,15 +378,24 @@ impl StatVinDpm {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatExitVap {
     NotActive = 0,
@@ -313,15 +407,24 @@ impl StatExitVap {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotActive,
             _ => Self::Active,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotActive),
+            1 => Ok(Self::Active),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum StatVapFail {
     NoFailure = 0,
@@ -333,15 +436,24 @@ impl StatVapFail {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NoFailure,
             _ => Self::Fail,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NoFailure),
+            1 => Ok(Self::Fail),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Tshut {
     NotTriggered = 0,
@@ -353,15 +465,24 @@ impl Tshut {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::NotTriggered,
             _ => Self::Triggered,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::NotTriggered),
+            1 => Ok(Self::Triggered),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ProchotClear {
     Clear = 0,
@@ -373,15 +494,24 @@ impl ProchotClear {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Clear,
             _ => Self::Idle,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Clear),
+            1 => Ok(Self::Idle),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum ProchotWidth {
     Width100ms = 0,
@@ -395,7 +525,7 @@ impl ProchotWidth {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Width100ms,
             1 => Self::Width58ms,
@@ -403,9 +533,20 @@ impl ProchotWidth {
             _ => Self::Width12_5ms,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Width100ms),
+            1 => Ok(Self::Width58ms),
+            2 => Ok(Self::Width6_15ms),
+            3 => Ok(Self::Width12_5ms),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum EnProchotExt {
     Disable = 0,
@@ -417,10 +558,18 @@ impl EnProchotExt {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,9 +1,12 @@
 use bitfield_struct::bitfield;
 
 /// Vin DPM Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct VinDpm {
+    #[bits(2, default = 0)]
+    reserved1_0: u8,
+
     /// Input voltage limit:
     ///
     /// Note: Writing value beyond clamp high/low will actually set
@@ -18,7 +21,7 @@ pub struct VinDpm {
     /// Clamped High
     ///
     /// Bit Step: 20mV
-    #[bits(13, default = 0xa0)]
+    #[bits(11, default = 0xa0)]
     pub voltage: u16,
 
     #[bits(3, default = 0)]
@@ -26,7 +29,38 @@ pub struct VinDpm {
 }
 
 impl VinDpm {
+    /// Lowest input voltage limit, in millivolts.
+    pub const MIN_MV: u16 = 3200;
+
+    /// Highest input voltage limit, in millivolts.
+    pub const MAX_MV: u16 = 27000;
+
+    const STEP_MV: u16 = 20;
+
     pub(crate) const fn addr() -> u8 {
         0x3d
     }
+
+    /// Input voltage limit in millivolts.
+    pub const fn millivolts(&self) -> u16 {
+        self.voltage() * Self::STEP_MV
+    }
+
+    /// Sets the input voltage limit in millivolts, clamped to the
+    /// register range like the charger itself does.
+    pub const fn with_millivolts(self, mv: u16) -> Self {
+        let mv = if mv < Self::MIN_MV {
+            Self::MIN_MV
+        } else if mv > Self::MAX_MV {
+            Self::MAX_MV
+        } else {
+            mv
+        };
+
+        self.with_voltage(mv / Self::STEP_MV)
+    }
 }
+
+impl_try_from_bits!(VinDpm {});
+
+impl_register!(VinDpm, 0x0280, ReadWrite);
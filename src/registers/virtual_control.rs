@@ -1,10 +1,10 @@
 use bitfield_struct::bitfield;
 
-use super::{AutoChg, RegReset, WdRst, WdTmrAdj};
+use super::{AutoChg, InvalidBits, RegReset, WdRst, WdTmrAdj};
 
 /// Virtual Control Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct VirtualControl {
     /// WATCHDOG Timer Adjust
     ///
@@ -93,7 +93,19 @@ impl VirtualControl {
     }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(VirtualControl {
+    wdtmr_adj: WdTmrAdj => (WDTMR_ADJ_OFFSET, WDTMR_ADJ_BITS),
+    wd_rst: WdRst => (WD_RST_OFFSET, WD_RST_BITS),
+    en_extilim: IlimHiZ => (EN_EXTILIM_OFFSET, EN_EXTILIM_BITS),
+    reg_reset: RegReset => (REG_RESET_OFFSET, REG_RESET_BITS),
+    en_otg: Otg => (EN_OTG_OFFSET, EN_OTG_BITS),
+    en_auto_chg: AutoChg => (EN_AUTO_CHG_OFFSET, EN_AUTO_CHG_BITS),
+});
+
+impl_register!(VirtualControl, 0x0013, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum IlimHiZ {
     Disable,
@@ -105,15 +117,24 @@ impl IlimHiZ {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Otg {
     Disable,
@@ -125,10 +146,18 @@ impl Otg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
@@ -1,8 +1,10 @@
 use bitfield_struct::bitfield;
 
+use super::InvalidBits;
+
 /// VMin Active Protection Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct VminActiveProtection {
     /// Fast Role Swap Feature Enable.
     ///
@@ -55,12 +57,73 @@ pub struct VminActiveProtection {
 }
 
 impl VminActiveProtection {
+    /// Lowest VSYS_TH2 threshold, in millivolts.
+    pub const VSYS_TH2_MIN_MV: u16 = 5000;
+
+    /// Highest VSYS_TH2 threshold, in millivolts.
+    pub const VSYS_TH2_MAX_MV: u16 = 11300;
+
+    /// Lowest VBUS_VAP_TH threshold, in millivolts.
+    pub const VBUS_VAP_TH_MIN_MV: u16 = 3200;
+
+    /// Highest VBUS_VAP_TH threshold, in millivolts.
+    pub const VBUS_VAP_TH_MAX_MV: u16 = 15900;
+
+    const STEP_MV: u16 = 100;
+
     pub(crate) const fn addr() -> u8 {
         0x37
     }
+
+    /// VSYS_TH2 threshold in millivolts.
+    pub const fn vsys_th2_mv(&self) -> u16 {
+        Self::VSYS_TH2_MIN_MV + self.vsys_th2() * Self::STEP_MV
+    }
+
+    /// Sets the VSYS_TH2 threshold in millivolts, clamped to the
+    /// register range.
+    pub const fn with_vsys_th2_mv(self, mv: u16) -> Self {
+        let mv = if mv < Self::VSYS_TH2_MIN_MV {
+            Self::VSYS_TH2_MIN_MV
+        } else if mv > Self::VSYS_TH2_MAX_MV {
+            Self::VSYS_TH2_MAX_MV
+        } else {
+            mv
+        };
+
+        self.with_vsys_th2((mv - Self::VSYS_TH2_MIN_MV) / Self::STEP_MV)
+    }
+
+    /// VBUS_VAP_TH threshold in millivolts.
+    pub const fn vbus_vap_th_mv(&self) -> u16 {
+        Self::VBUS_VAP_TH_MIN_MV + self.vbus_vap_th() * Self::STEP_MV
+    }
+
+    /// Sets the VBUS_VAP_TH threshold in millivolts, clamped to the
+    /// register range.
+    pub const fn with_vbus_vap_th_mv(self, mv: u16) -> Self {
+        let mv = if mv < Self::VBUS_VAP_TH_MIN_MV {
+            Self::VBUS_VAP_TH_MIN_MV
+        } else if mv > Self::VBUS_VAP_TH_MAX_MV {
+            Self::VBUS_VAP_TH_MAX_MV
+        } else {
+            mv
+        };
+
+        self.with_vbus_vap_th((mv - Self::VBUS_VAP_TH_MIN_MV) / Self::STEP_MV)
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+impl_try_from_bits!(VminActiveProtection {
+    en_frs: FastRoleSwap => (EN_FRS_OFFSET, EN_FRS_BITS),
+    en_vsysth2_follow_vsysth1: Follow => (EN_VSYSTH2_FOLLOW_VSYSTH1_OFFSET, EN_VSYSTH2_FOLLOW_VSYSTH1_BITS),
+    dis_batovp_20ma: BatOvpDchrg => (DIS_BATOVP_20MA_OFFSET, DIS_BATOVP_20MA_BITS),
+});
+
+impl_register!(VminActiveProtection, 0x0024, ReadWrite);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum FastRoleSwap {
     Disable,
@@ -72,15 +135,24 @@ impl FastRoleSwap {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Follow {
     Disable,
@@ -92,15 +164,24 @@ impl Follow {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Disable,
             _ => Self::Enable,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Disable),
+            1 => Ok(Self::Enable),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum BatOvpDchrg {
     Dchrg20mA,
@@ -112,10 +193,18 @@ impl BatOvpDchrg {
         self as _
     }
 
-    const fn from_bits(value: u8) -> Self {
+    pub(crate) const fn from_bits(value: u8) -> Self {
         match value {
             0 => Self::Dchrg20mA,
             _ => Self::NoDchrg20mA,
         }
     }
+
+    pub const fn try_from_bits(value: u8) -> Result<Self, InvalidBits> {
+        match value {
+            0 => Ok(Self::Dchrg20mA),
+            1 => Ok(Self::NoDchrg20mA),
+            _ => Err(InvalidBits(value)),
+        }
+    }
 }
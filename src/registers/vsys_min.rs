@@ -1,8 +1,8 @@
 use bitfield_struct::bitfield;
 
 /// Vsys Min Register
-#[bitfield(u16)]
-#[derive(PartialEq)]
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
 pub struct VsysMin {
     /// Minimum system voltage configuration register
     ///
@@ -42,7 +42,38 @@ pub struct VsysMin {
 }
 
 impl VsysMin {
+    /// Lowest minimum system voltage, in millivolts.
+    pub const MIN_MV: u16 = 5000;
+
+    /// Highest minimum system voltage, in millivolts.
+    pub const MAX_MV: u16 = 21000;
+
+    const STEP_MV: u16 = 5;
+
     pub(crate) const fn addr() -> u8 {
         0x3e
     }
+
+    /// Minimum system voltage in millivolts.
+    pub const fn millivolts(&self) -> u16 {
+        self.voltage() * Self::STEP_MV
+    }
+
+    /// Sets the minimum system voltage in millivolts, clamped to the
+    /// register range like the charger itself does.
+    pub const fn with_millivolts(self, mv: u16) -> Self {
+        let mv = if mv < Self::MIN_MV {
+            Self::MIN_MV
+        } else if mv > Self::MAX_MV {
+            Self::MAX_MV
+        } else {
+            mv
+        };
+
+        self.with_voltage(mv / Self::STEP_MV)
+    }
 }
+
+impl_try_from_bits!(VsysMin {});
+
+impl_register!(VsysMin, 0x0528, ReadWrite);
@@ -0,0 +1,252 @@
+//! Smart Battery System (SBS) charger emulation: translating the
+//! ChargingCurrent, ChargingVoltage and AlarmWarning broadcasts a smart
+//! battery sends to the charger address into charger writes.
+//!
+//! The charger itself doesn't take SBS broadcasts; whatever receives
+//! them on the SMBus hands them to [`Bq2577x::sbs_broadcast`].
+
+use bitfield_struct::bitfield;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{ChargeCurrent, ChargeVoltage};
+use crate::{Bq2577x, Error};
+
+/// SBS ChargingCurrent command code.
+pub const CHARGING_CURRENT: u8 = 0x14;
+
+/// SBS ChargingVoltage command code.
+pub const CHARGING_VOLTAGE: u8 = 0x15;
+
+/// SBS AlarmWarning command code.
+pub const ALARM_WARNING: u8 = 0x16;
+
+/// AlarmWarning broadcast, in the SBS BatteryStatus format.
+#[bitfield(u16, defmt = cfg(feature = "defmt"))]
+#[derive(PartialEq, Eq, Hash)]
+pub struct AlarmWarning {
+    /// Error code of the last battery command.
+    #[bits(4)]
+    pub error_code: u8,
+
+    pub fully_discharged: bool,
+    pub fully_charged: bool,
+    pub discharging: bool,
+    pub initialized: bool,
+    pub remaining_time_alarm: bool,
+    pub remaining_capacity_alarm: bool,
+
+    #[bits(1)]
+    reserved10: bool,
+
+    pub terminate_discharge_alarm: bool,
+    pub over_temp_alarm: bool,
+
+    #[bits(1)]
+    reserved13: bool,
+
+    pub terminate_charge_alarm: bool,
+    pub over_charged_alarm: bool,
+}
+
+impl AlarmWarning {
+    /// Whether an alarm requires the charger to stop charging.
+    pub const fn stops_charging(&self) -> bool {
+        self.over_charged_alarm() || self.terminate_charge_alarm() || self.over_temp_alarm()
+    }
+}
+
+/// SBS broadcast addressed to the charger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SbsBroadcast {
+    /// Requested charge current in milliamps; 0 stops charging and
+    /// 0xFFFF requests the most the charger can deliver.
+    ChargingCurrent(u16),
+
+    /// Requested charge voltage in millivolts.
+    ChargingVoltage(u16),
+
+    AlarmWarning(AlarmWarning),
+}
+
+impl SbsBroadcast {
+    /// Decodes a write word broadcast, or returns `None` for a command
+    /// the charger doesn't take.
+    pub const fn parse(command: u8, word: u16) -> Option<Self> {
+        match command {
+            CHARGING_CURRENT => Some(Self::ChargingCurrent(word)),
+            CHARGING_VOLTAGE => Some(Self::ChargingVoltage(word)),
+            ALARM_WARNING => Some(Self::AlarmWarning(AlarmWarning::from_bits(word))),
+            _ => None,
+        }
+    }
+}
+
+/// SBS bridge state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SbsBridge {
+    /// Last requested charge current, in milliamps.
+    current_ma: Option<u16>,
+
+    /// Alarm holding charging off.
+    alarm: Option<AlarmWarning>,
+}
+
+impl SbsBridge {
+    pub const fn new() -> Self {
+        Self {
+            current_ma: None,
+            alarm: None,
+        }
+    }
+
+    /// Alarm holding charging off, if any.
+    pub const fn alarm(&self) -> Option<AlarmWarning> {
+        self.alarm
+    }
+
+    /// Last requested charge current in milliamps, whether or not an
+    /// alarm holds charging off.
+    pub const fn requested_current_ma(&self) -> Option<u16> {
+        self.current_ma
+    }
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Applies a smart battery broadcast. ChargingCurrent and
+    /// ChargingVoltage are scaled for the sense resistor in use and
+    /// clamped to the charger's range. An AlarmWarning with an over
+    /// charge, terminate charge or over temperature alarm stops charging
+    /// until an AlarmWarning without them, or [`Self::sbs_clear_alarm`],
+    /// resumes the last requested current.
+    pub async fn sbs_broadcast(
+        &mut self,
+        bridge: &mut SbsBridge,
+        broadcast: SbsBroadcast,
+    ) -> Result<(), Error<IFACE::Error>> {
+        match broadcast {
+            SbsBroadcast::ChargingCurrent(ma) => {
+                bridge.current_ma = Some(ma);
+                self.sbs_apply_current(bridge).await
+            }
+            SbsBroadcast::ChargingVoltage(mv) => {
+                self.set_charge_voltage(ChargeVoltage::new().with_millivolts(mv)).await
+            }
+            SbsBroadcast::AlarmWarning(alarm) if alarm.stops_charging() => {
+                bridge.alarm = Some(alarm);
+                self.sbs_apply_current(bridge).await
+            }
+            SbsBroadcast::AlarmWarning(_) => self.sbs_clear_alarm(bridge).await,
+        }
+    }
+
+    /// Releases an alarm holding charging off and resumes the last
+    /// requested current.
+    pub async fn sbs_clear_alarm(&mut self, bridge: &mut SbsBridge) -> Result<(), Error<IFACE::Error>> {
+        if bridge.alarm.take().is_none() {
+            return Ok(());
+        }
+
+        self.sbs_apply_current(bridge).await
+    }
+
+    async fn sbs_apply_current(&mut self, bridge: &SbsBridge) -> Result<(), Error<IFACE::Error>> {
+        let ma = match (bridge.alarm, bridge.current_ma) {
+            (None, Some(ma)) => ma,
+            (Some(_), _) => 0,
+            (None, None) => return Ok(()),
+        };
+
+        let rsr = self.charge_option1().await?.rsns_rsr();
+
+        self.set_charge_current(ChargeCurrent::new().with_milliamps(ma, rsr))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargeOption1, Register, RsnsRsr};
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            SbsBroadcast::parse(0x14, 2000),
+            Some(SbsBroadcast::ChargingCurrent(2000))
+        );
+        assert_eq!(
+            SbsBroadcast::parse(0x15, 12600),
+            Some(SbsBroadcast::ChargingVoltage(12600))
+        );
+        assert_eq!(SbsBroadcast::parse(0x0d, 50), None);
+
+        let Some(SbsBroadcast::AlarmWarning(alarm)) = SbsBroadcast::parse(0x16, 0x40c0) else {
+            panic!("not an alarm");
+        };
+
+        assert!(alarm.terminate_charge_alarm());
+        assert!(alarm.initialized());
+        assert!(alarm.discharging());
+        assert!(alarm.stops_charging());
+        assert!(!AlarmWarning::from_bits(0x0a80).stops_charging());
+    }
+
+    #[tokio::test]
+    async fn test_sbs_bridge() {
+        let option1 = ChargeOption1::from_bits(ChargeOption1::RESET).with_rsns_rsr(RsnsRsr::MilliOhms2);
+
+        // 2mΩ RSR: 20mA steps
+        let e = vec![
+            write::<ChargeVoltage>(ChargeVoltage::new().with_voltage(12600 / 4).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(2000 / 20).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(0),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(0),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(ChargeCurrent::new().with_current(1500 / 20).into_bits()),
+            read::<ChargeOption1>(option1.into_bits()),
+            write::<ChargeCurrent>(0),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let mut bridge = SbsBridge::new();
+        let over_temp = AlarmWarning::new().with_over_temp_alarm(true);
+
+        for broadcast in [
+            SbsBroadcast::ChargingVoltage(12600),
+            SbsBroadcast::ChargingCurrent(2000),
+            SbsBroadcast::AlarmWarning(over_temp),
+            // held off by the alarm
+            SbsBroadcast::ChargingCurrent(1500),
+        ] {
+            bq.sbs_broadcast(&mut bridge, broadcast).await.unwrap();
+        }
+
+        assert_eq!(bridge.alarm(), Some(over_temp));
+        assert_eq!(bridge.requested_current_ma(), Some(1500));
+
+        bq.sbs_broadcast(&mut bridge, SbsBroadcast::AlarmWarning(AlarmWarning::new()))
+            .await
+            .unwrap();
+        assert_eq!(bridge.alarm(), None);
+
+        // no alarm to clear
+        bq.sbs_clear_alarm(&mut bridge).await.unwrap();
+
+        bq.sbs_broadcast(&mut bridge, SbsBroadcast::ChargingCurrent(0))
+            .await
+            .unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
@@ -0,0 +1,326 @@
+//! NTC thermistor on CMPIN_TR, converting the pin voltage to temperature
+//! and sizing the bias network for the thermal regulation (TREG) trip
+//! point.
+
+use crate::interface::RegisterInterface;
+use crate::registers::{EnTReg, Function, PPThermal, TRegDeg};
+use crate::{Bq2577x, Error};
+
+/// Offset between degrees Celsius and kelvin.
+const KELVIN: f32 = 273.15;
+
+/// CMPIN_TR voltage below which TREG trips, in millivolts, from
+/// [`crate::registers::AutoCharge::stat_thermal`].
+pub const TREG_THRESHOLD_MV: f32 = 1100.0;
+
+/// How far from the target temperature a bias network may trip TREG,
+/// either way, in degrees Celsius, to allow for rounding in the
+/// network's values.
+const TREG_TOLERANCE_C: f32 = 0.5;
+
+/// Thermistor resistance model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThermistorModel {
+    /// Beta model from the resistance at 25°C and the Beta constant.
+    Beta { r25_ohms: f32, beta: f32 },
+
+    /// Steinhart–Hart model, `1/T = a + b ln(R) + c ln(R)³` with T in
+    /// kelvin.
+    SteinhartHart { a: f32, b: f32, c: f32 },
+}
+
+impl ThermistorModel {
+    /// Thermistor temperature at `ohms`, in degrees Celsius.
+    pub fn celsius(&self, ohms: f32) -> f32 {
+        let inverse = match *self {
+            Self::Beta { r25_ohms, beta } => 1.0 / (25.0 + KELVIN) + libm::logf(ohms / r25_ohms) / beta,
+            Self::SteinhartHart { a, b, c } => {
+                let ln = libm::logf(ohms);
+                a + b * ln + c * ln * ln * ln
+            }
+        };
+
+        1.0 / inverse - KELVIN
+    }
+
+    /// Thermistor resistance at `celsius`, in ohms.
+    pub fn ohms(&self, celsius: f32) -> f32 {
+        let inverse = 1.0 / (celsius + KELVIN);
+
+        match *self {
+            Self::Beta { r25_ohms, beta } => r25_ohms * libm::expf(beta * (inverse - 1.0 / (25.0 + KELVIN))),
+            Self::SteinhartHart { a, b, c } => {
+                // Solve the cubic in ln(R) with Cardano's formula.
+                let x = (a - inverse) / c;
+                let y = libm::sqrtf(libm::powf(b / (3.0 * c), 3.0) + x * x / 4.0);
+
+                libm::expf(libm::cbrtf(y - x / 2.0) - libm::cbrtf(y + x / 2.0))
+            }
+        }
+    }
+}
+
+/// Bias network: a pull-up from `supply_mv` to CMPIN_TR and the
+/// thermistor from CMPIN_TR to ground, optionally with a resistor in
+/// parallel to linearize it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Thermistor {
+    pub model: ThermistorModel,
+
+    /// Bias supply voltage, in millivolts.
+    pub supply_mv: f32,
+
+    /// Pull-up resistor, in ohms.
+    pub pullup_ohms: f32,
+
+    /// Resistor in parallel with the thermistor, in ohms.
+    pub parallel_ohms: Option<f32>,
+}
+
+impl Thermistor {
+    pub const fn new(model: ThermistorModel, supply_mv: f32, pullup_ohms: f32) -> Self {
+        Self {
+            model,
+            supply_mv,
+            pullup_ohms,
+            parallel_ohms: None,
+        }
+    }
+
+    pub const fn with_parallel(self, ohms: f32) -> Self {
+        Self {
+            parallel_ohms: Some(ohms),
+            ..self
+        }
+    }
+
+    /// Sizes the pull-up so that TREG trips at `celsius`. When
+    /// rounding to a standard value, take the nearest one: a larger
+    /// pull-up trips at a lower temperature, and
+    /// [`Bq2577x::configure_treg`] rejects networks that trip more than
+    /// half a degree either side of the target.
+    pub fn for_treg(model: ThermistorModel, supply_mv: f32, parallel_ohms: Option<f32>, celsius: f32) -> Self {
+        let network = Self {
+            model,
+            supply_mv,
+            pullup_ohms: 0.0,
+            parallel_ohms,
+        };
+
+        let bottom = network.bottom_ohms(model.ohms(celsius));
+        let pullup_ohms = bottom * (supply_mv - TREG_THRESHOLD_MV) / TREG_THRESHOLD_MV;
+
+        Self { pullup_ohms, ..network }
+    }
+
+    /// Resistance from CMPIN_TR to ground for a thermistor resistance.
+    fn bottom_ohms(&self, ntc_ohms: f32) -> f32 {
+        match self.parallel_ohms {
+            Some(parallel) => ntc_ohms * parallel / (ntc_ohms + parallel),
+            None => ntc_ohms,
+        }
+    }
+
+    /// CMPIN_TR voltage at `celsius`, in millivolts.
+    pub fn pin_mv(&self, celsius: f32) -> f32 {
+        let bottom = self.bottom_ohms(self.model.ohms(celsius));
+
+        self.supply_mv * bottom / (bottom + self.pullup_ohms)
+    }
+
+    /// Temperature for a CMPIN_TR voltage, in degrees Celsius, or
+    /// `None` if the voltage is outside what the network can produce,
+    /// e.g. with the thermistor open or shorted.
+    pub fn celsius(&self, pin_mv: f32) -> Option<f32> {
+        if pin_mv <= 0.0 || pin_mv >= self.supply_mv {
+            return None;
+        }
+
+        let bottom = self.pullup_ohms * pin_mv / (self.supply_mv - pin_mv);
+
+        let ntc_ohms = match self.parallel_ohms {
+            Some(parallel) if bottom >= parallel => return None,
+            Some(parallel) => bottom * parallel / (parallel - bottom),
+            None => bottom,
+        };
+
+        Some(self.model.celsius(ntc_ohms))
+    }
+
+    /// Temperature at which TREG trips with this network, in degrees
+    /// Celsius.
+    pub fn treg_celsius(&self) -> Option<f32> {
+        self.celsius(TREG_THRESHOLD_MV)
+    }
+}
+
+/// Thermal regulation (TREG) configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TregConfig {
+    /// Deglitch time before the PROCHOT profile is triggered.
+    pub deglitch: TRegDeg,
+
+    /// Assert PROCHOT on TREG.
+    pub prochot: bool,
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Reads the thermistor temperature from the CMPIN ADC channel, in
+    /// degrees Celsius. See [`Thermistor::celsius`].
+    pub async fn thermistor_celsius(&mut self, thermistor: &Thermistor) -> Result<Option<f32>, Error<IFACE::Error>> {
+        let pin_mv = self.adc_cmpin_tr().await?.voltage();
+
+        Ok(thermistor.celsius(pin_mv as f32))
+    }
+
+    /// Routes CMPIN_TR to thermal regulation and enables it, for TREG
+    /// to trip at `celsius`. The trip temperature is set by the bias
+    /// network, so this returns the temperature at which `thermistor`
+    /// trips, in degrees Celsius. Fails with [`Error::OutOfRange`],
+    /// without touching the charger, if the network never trips or trips
+    /// more than half a degree above or below `celsius`: too high lets
+    /// the battery overheat, too low throttles charging at normal
+    /// temperatures. [`Thermistor::for_treg`] gives the pull-up needed.
+    pub async fn configure_treg(
+        &mut self,
+        thermistor: &Thermistor,
+        celsius: f32,
+        config: TregConfig,
+    ) -> Result<f32, Error<IFACE::Error>> {
+        let trip = match thermistor.treg_celsius() {
+            Some(trip) if trip <= celsius + TREG_TOLERANCE_C && trip >= celsius - TREG_TOLERANCE_C => trip,
+            _ => return Err(Error::OutOfRange),
+        };
+
+        let option5 = self.charge_option5().await?;
+        self.set_charge_option5(option5.with_cmpin_tr_select(Function::Treg))
+            .await?;
+
+        let pp_thermal = if config.prochot {
+            PPThermal::Enable
+        } else {
+            PPThermal::Disable
+        };
+
+        let auto_charge = self.auto_charge().await?;
+        self.set_auto_charge(
+            auto_charge
+                .with_thermal_deg(config.deglitch)
+                .with_pp_thermal(pp_thermal)
+                .with_en_treg(EnTReg::Enable),
+        )
+        .await?;
+
+        Ok(trip)
+    }
+
+    /// Disables thermal regulation.
+    pub async fn disable_treg(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let auto_charge = self.auto_charge().await?;
+        self.set_auto_charge(auto_charge.with_en_treg(EnTReg::Disable)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{AdcCmpinTr, AutoCharge, ChargeOption5, Register};
+    use crate::tests::{read, write};
+
+    const BETA: ThermistorModel = ThermistorModel::Beta {
+        r25_ohms: 10_000.0,
+        beta: 3435.0,
+    };
+
+    // typical 10k NTC coefficients
+    const STEINHART_HART: ThermistorModel = ThermistorModel::SteinhartHart {
+        a: 1.009_249_5e-3,
+        b: 2.378_405_4e-4,
+        c: 2.019_202_7e-7,
+    };
+
+    fn assert_close(a: f32, b: f32, tolerance: f32) {
+        assert!((a - b).abs() < tolerance, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_models() {
+        assert_close(BETA.celsius(10_000.0), 25.0, 0.01);
+        assert_close(STEINHART_HART.celsius(10_000.0), 25.0, 0.5);
+
+        for model in [BETA, STEINHART_HART] {
+            for celsius in [-20.0, 0.0, 25.0, 60.0, 100.0] {
+                assert_close(model.celsius(model.ohms(celsius)), celsius, 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_network() {
+        let thermistor = Thermistor::new(BETA, 5000.0, 10_000.0);
+        assert_close(thermistor.pin_mv(25.0), 2500.0, 0.5);
+        assert_close(thermistor.celsius(2500.0).unwrap(), 25.0, 0.01);
+        assert_eq!(thermistor.celsius(0.0), None);
+        assert_eq!(thermistor.celsius(5000.0), None);
+
+        let thermistor = thermistor.with_parallel(20_000.0);
+        assert_close(thermistor.celsius(thermistor.pin_mv(40.0)).unwrap(), 40.0, 0.05);
+
+        let thermistor = Thermistor::for_treg(BETA, 5000.0, Some(100_000.0), 70.0);
+        assert_close(thermistor.pin_mv(70.0), TREG_THRESHOLD_MV, 0.5);
+        assert_close(thermistor.treg_celsius().unwrap(), 70.0, 0.05);
+    }
+
+    #[tokio::test]
+    async fn test_configure_treg() {
+        let option5 = ChargeOption5::from_bits(ChargeOption5::RESET);
+        let auto_charge = AutoCharge::from_bits(AutoCharge::RESET);
+
+        let e = vec![
+            read::<ChargeOption5>(option5.into_bits()),
+            write::<ChargeOption5>(option5.with_cmpin_tr_select(Function::Treg).into_bits()),
+            read::<AutoCharge>(auto_charge.into_bits()),
+            write::<AutoCharge>(
+                auto_charge
+                    .with_thermal_deg(TRegDeg::Time121ms)
+                    .with_pp_thermal(PPThermal::Enable)
+                    .with_en_treg(EnTReg::Enable)
+                    .into_bits(),
+            ),
+            read::<AdcCmpinTr>(2500),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = TregConfig {
+            deglitch: TRegDeg::Time121ms,
+            prochot: true,
+        };
+
+        let network = Thermistor::for_treg(BETA, 5000.0, None, 70.0);
+        assert_close(bq.configure_treg(&network, 70.0, config).await.unwrap(), 70.0, 0.05);
+
+        // trips at about 62°C, too late for 50°C and too early for 70°C
+        let thermistor = Thermistor::new(BETA, 5000.0, 10_000.0);
+        assert_eq!(
+            bq.configure_treg(&thermistor, 50.0, config).await,
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            bq.configure_treg(&thermistor, 70.0, config).await,
+            Err(Error::OutOfRange)
+        );
+
+        let celsius = bq.thermistor_celsius(&thermistor).await.unwrap().unwrap();
+        assert_close(celsius, 25.0, 0.01);
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}
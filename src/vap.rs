@@ -0,0 +1,294 @@
+//! Vmin Active Protection (VAP), the hybrid power mode in which the
+//! battery charges the input capacitors so the adapter and battery can
+//! both supply a system load peak.
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::RegisterInterface;
+use crate::registers::{
+    BatOvpDchrg, ChargeOption4, EnOtg, Follow, OtgVapMode, PPVbusVap, ProchotStatus, StatExitVap, StatVapFail,
+    StatVbusVap, VminActiveProtection,
+};
+use crate::{Bq2577x, Error};
+
+/// VAP thresholds in millivolts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VapConfig {
+    /// VSYS_TH2 threshold, 5000mV to 11300mV, or `None` to follow
+    /// VSYS_TH1.
+    pub vsys_th2_mv: Option<u16>,
+
+    /// VBUS threshold below which PROCHOT is asserted, 3200mV to
+    /// 15900mV.
+    pub vbus_vap_th_mv: u16,
+
+    /// Assert PROCHOT when VBUS drops below `vbus_vap_th_mv`.
+    pub prochot_on_vbus: bool,
+
+    /// Discharge 20mA through VSYS on battery over-voltage.
+    pub batovp_discharge: bool,
+}
+
+/// State of the charger with respect to VAP mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum VapOutcome {
+    /// Still in VAP mode.
+    Active,
+
+    /// Still in VAP mode, but VBUS dropped below the VBUS_VAP
+    /// threshold.
+    VbusLow,
+
+    /// VAP mode was left, either by the host or because of a charger
+    /// fault. PROCHOT stays asserted until [`Bq2577x::clear_vap_status`].
+    Exited,
+
+    /// VBUS couldn't be loaded 7 consecutive times, so the charger left
+    /// VAP mode and stays latched off until
+    /// [`Bq2577x::clear_vap_status`]. The battery voltage may be too
+    /// low, or the VAP loading current too high.
+    Failed,
+}
+
+impl<IFACE: RegisterInterface> Bq2577x<IFACE> {
+    /// Programs the VAP thresholds. Fails with [`Error::OutOfRange`] if
+    /// a threshold is outside the register range.
+    pub async fn configure_vap(&mut self, config: VapConfig) -> Result<(), Error<IFACE::Error>> {
+        let in_range = |mv, min, max| {
+            if (min..=max).contains(&mv) {
+                Ok(mv)
+            } else {
+                Err(Error::OutOfRange)
+            }
+        };
+
+        let vbus_mv = in_range(
+            config.vbus_vap_th_mv,
+            VminActiveProtection::VBUS_VAP_TH_MIN_MV,
+            VminActiveProtection::VBUS_VAP_TH_MAX_MV,
+        )?;
+
+        let mut vap = self
+            .vmin_active_protection()
+            .await?
+            .with_vbus_vap_th_mv(vbus_mv)
+            .with_en_vsysth2_follow_vsysth1(Follow::Enable);
+
+        if let Some(mv) = config.vsys_th2_mv {
+            let mv = in_range(
+                mv,
+                VminActiveProtection::VSYS_TH2_MIN_MV,
+                VminActiveProtection::VSYS_TH2_MAX_MV,
+            )?;
+
+            vap = vap.with_vsys_th2_mv(mv).with_en_vsysth2_follow_vsysth1(Follow::Disable);
+        }
+
+        let batovp = if config.batovp_discharge {
+            BatOvpDchrg::Dchrg20mA
+        } else {
+            BatOvpDchrg::NoDchrg20mA
+        };
+
+        self.set_vmin_active_protection(vap.with_dis_batovp_20ma(batovp))
+            .await?;
+
+        let pp_vbus_vap = if config.prochot_on_vbus {
+            PPVbusVap::Enable
+        } else {
+            PPVbusVap::Disable
+        };
+
+        let option4 = self.option4_read().await?;
+        self.set_charge_option4(option4.with_pp_vbus_vap(pp_vbus_vap)).await
+    }
+
+    /// Clears the latched VAP exit and failure status, releasing
+    /// PROCHOT and, after a failure, letting VAP mode be entered again.
+    pub async fn clear_vap_status(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let status = self.prochot_read().await?;
+        self.set_prochot_status(
+            status
+                .with_stat_exit_vap(StatExitVap::NotActive)
+                .with_stat_vap_fail(StatVapFail::NoFailure),
+        )
+        .await
+    }
+
+    /// Hands the EN_OTG pin to VAP mode and enables it, after clearing
+    /// any stale VAP status. The charger enters VAP mode while the
+    /// EN_OTG pin is high.
+    pub async fn enter_vap(&mut self) -> Result<(), Error<IFACE::Error>> {
+        self.clear_vap_status().await?;
+
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(
+            option3
+                .with_otg_vap_mode(OtgVapMode::VapMode)
+                .with_en_otg(EnOtg::Enable),
+        )
+        .await
+    }
+
+    /// Leaves VAP mode and hands the EN_OTG pin back to OTG mode.
+    pub async fn exit_vap(&mut self) -> Result<(), Error<IFACE::Error>> {
+        let option3 = self.charge_option3().await?;
+        self.set_charge_option3(option3.with_en_otg(EnOtg::Disable)).await?;
+        self.set_charge_option3(
+            option3
+                .with_en_otg(EnOtg::Disable)
+                .with_otg_vap_mode(OtgVapMode::OtgMode),
+        )
+        .await
+    }
+
+    /// Reads the VAP status.
+    pub async fn vap_status(&mut self) -> Result<VapOutcome, Error<IFACE::Error>> {
+        let status = self.prochot_take(ProchotStatus::new()).await?;
+
+        if status.stat_vap_fail() == StatVapFail::Fail {
+            return Ok(VapOutcome::Failed);
+        }
+
+        if status.stat_exit_vap() == StatExitVap::Active {
+            return Ok(VapOutcome::Exited);
+        }
+
+        let vbus_vap = ChargeOption4::new().with_stat_vbus_vap(StatVbusVap::Triggered);
+
+        if self.option4_take(vbus_vap).await?.stat_vbus_vap() == StatVbusVap::Triggered {
+            return Ok(VapOutcome::VbusLow);
+        }
+
+        Ok(VapOutcome::Active)
+    }
+
+    /// Polls the VAP status every `poll_interval_ms` until it is no
+    /// longer [`VapOutcome::Active`] and returns it, failing with
+    /// [`Error::Timeout`] after `max_polls` polls.
+    pub async fn monitor_vap<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_ms: u32,
+        max_polls: u32,
+    ) -> Result<VapOutcome, Error<IFACE::Error>> {
+        for _ in 0..max_polls {
+            delay.delay_ms(poll_interval_ms).await;
+
+            let outcome = self.vap_status().await?;
+
+            if outcome != VapOutcome::Active {
+                return Ok(outcome);
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::i2c::Mock;
+
+    use super::*;
+    use crate::registers::{ChargeOption3, ProchotStatus, Register};
+    use crate::tests::{read, write};
+
+    #[test]
+    fn test_vap_units() {
+        let vap = VminActiveProtection::from_bits(VminActiveProtection::RESET);
+        assert_eq!(vap.vsys_th2_mv(), 5900);
+        assert_eq!(vap.vbus_vap_th_mv(), 3200);
+
+        assert_eq!(vap.with_vsys_th2_mv(7000).vsys_th2(), 20);
+        assert_eq!(vap.with_vsys_th2_mv(20000).vsys_th2_mv(), 11300);
+        assert_eq!(vap.with_vbus_vap_th_mv(15900).vbus_vap_th(), 0x7f);
+        assert_eq!(vap.with_vbus_vap_th_mv(0).vbus_vap_th_mv(), 3200);
+    }
+
+    #[tokio::test]
+    async fn test_configure_vap() {
+        let vap = VminActiveProtection::from_bits(VminActiveProtection::RESET);
+        let option4 = ChargeOption4::from_bits(ChargeOption4::RESET);
+
+        let e = vec![
+            read::<VminActiveProtection>(vap.into_bits()),
+            write::<VminActiveProtection>(
+                vap.with_vbus_vap_th_mv(6000)
+                    .with_vsys_th2_mv(7000)
+                    .with_en_vsysth2_follow_vsysth1(Follow::Disable)
+                    .with_dis_batovp_20ma(BatOvpDchrg::NoDchrg20mA)
+                    .into_bits(),
+            ),
+            read::<ChargeOption4>(option4.into_bits()),
+            write::<ChargeOption4>(option4.with_pp_vbus_vap(PPVbusVap::Enable).into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        let config = VapConfig {
+            vsys_th2_mv: Some(7000),
+            vbus_vap_th_mv: 6000,
+            prochot_on_vbus: true,
+            batovp_discharge: false,
+        };
+
+        bq.configure_vap(config).await.unwrap();
+
+        let config = VapConfig {
+            vbus_vap_th_mv: 20000,
+            ..config
+        };
+
+        assert_eq!(bq.configure_vap(config).await, Err(Error::OutOfRange));
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+
+    #[tokio::test]
+    async fn test_vap_cycle() {
+        let status = ProchotStatus::from_bits(ProchotStatus::RESET);
+        let failed = status.with_stat_vap_fail(StatVapFail::Fail);
+        let option3 = ChargeOption3::from_bits(ChargeOption3::RESET);
+        let vap = option3
+            .with_otg_vap_mode(OtgVapMode::VapMode)
+            .with_en_otg(EnOtg::Enable);
+        let option4 = ChargeOption4::from_bits(ChargeOption4::RESET);
+
+        let e = vec![
+            read::<ProchotStatus>(failed.into_bits()),
+            write::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption3>(option3.into_bits()),
+            write::<ChargeOption3>(vap.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.with_stat_vbus_vap(StatVbusVap::Triggered).into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.into_bits()),
+            read::<ProchotStatus>(status.into_bits()),
+            read::<ChargeOption4>(option4.into_bits()),
+            read::<ProchotStatus>(failed.into_bits()),
+            read::<ChargeOption3>(vap.into_bits()),
+            write::<ChargeOption3>(vap.with_en_otg(EnOtg::Disable).into_bits()),
+            write::<ChargeOption3>(option3.into_bits()),
+        ];
+
+        let mock = Mock::new(&e);
+        let mut bq = Bq2577x::new(mock);
+
+        bq.enter_vap().await.unwrap();
+        assert_eq!(bq.monitor_vap(&mut NoopDelay, 10, 5).await, Ok(VapOutcome::VbusLow));
+        assert_eq!(bq.monitor_vap(&mut NoopDelay, 10, 2).await, Err(Error::Timeout));
+        assert_eq!(bq.monitor_vap(&mut NoopDelay, 10, 5).await, Ok(VapOutcome::Failed));
+        bq.exit_vap().await.unwrap();
+
+        let mut mock = bq.destroy();
+        mock.done();
+    }
+}